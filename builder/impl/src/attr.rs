@@ -0,0 +1,232 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::meta::ParseNestedMeta;
+use syn::{
+    parenthesized, parse_quote, token, Attribute, Error, Expr, ExprLit, Ident, Lit, LitStr, Path,
+    Result, Token,
+};
+
+const CONTAINER_KEYS: &[&str] = &["crate", "with_methods"];
+const FIELD_KEYS: &[&str] = &["each", "default"];
+
+pub struct Container {
+    pub krate: Path,
+    // Path of the `with_methods` key if present, for error reporting.
+    pub with_methods: Option<Path>,
+}
+
+pub struct Field {
+    pub each: Option<Ident>,
+    pub default: bool,
+}
+
+// Collects every error found while processing the input so that they can all
+// be reported together, rather than making the caller fix them one at a time.
+#[derive(Default)]
+pub struct Errors {
+    error: Option<Error>,
+}
+
+impl Errors {
+    pub fn push(&mut self, error: Error) {
+        match &mut self.error {
+            Some(existing) => existing.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    // Emitted next to a best-effort expansion rather than in place of it, so
+    // that a mistake in one attribute does not also make the builder disappear
+    // from the caller's IDE and produce errors at every use of it.
+    pub fn into_compile_error(self) -> Option<TokenStream> {
+        self.error.map(Error::into_compile_error)
+    }
+
+    // For input too malformed to expand at all.
+    pub fn into_error(self) -> Option<Error> {
+        self.error
+    }
+}
+
+// Attributes that fail to parse are reported into `errors` and otherwise
+// treated as if they were absent.
+pub fn container(attrs: &[Attribute], errors: &mut Errors) -> Container {
+    let mut container = Container::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("builder") {
+            continue;
+        }
+
+        let result = attr.parse_nested_meta(|meta| container.parse_meta(meta, errors));
+        if let Err(err) = result {
+            errors.push(err);
+        }
+    }
+
+    container
+}
+
+impl Container {
+    // Also used directly on the arguments of the #[builder] attribute macro,
+    // which accepts the same keys as the derive's container attribute.
+    pub fn parse_meta(&mut self, meta: ParseNestedMeta, errors: &mut Errors) -> Result<()> {
+        if meta.path.is_ident("crate") {
+            if let Some(krate) = string_value(&meta, errors)? {
+                match krate.parse_with(Path::parse_mod_style) {
+                    Ok(krate) => self.krate = krate,
+                    Err(err) => errors.push(err),
+                }
+            }
+        } else if meta.path.is_ident("with_methods") {
+            if meta.input.peek(Token![=]) {
+                let value: Expr = meta.value()?.parse()?;
+                let msg = "`with_methods` does not take a value";
+                errors.push(Error::new_spanned(value, msg));
+            } else if self.with_methods.is_some() {
+                errors.push(meta.error("duplicate `with_methods` attribute"));
+            } else {
+                self.with_methods = Some(meta.path);
+            }
+        } else {
+            errors.push(unknown_key(&meta, CONTAINER_KEYS));
+            skip_value(&meta)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Container {
+            krate: parse_quote!(::derive_builder),
+            with_methods: None,
+        }
+    }
+}
+
+pub fn field(attrs: &[Attribute], errors: &mut Errors) -> Field {
+    let mut field = Field {
+        each: None,
+        default: false,
+    };
+
+    for attr in attrs {
+        if !attr.path().is_ident("builder") {
+            continue;
+        }
+
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("each") {
+                if let Some(each) = string_value(&meta, errors)? {
+                    match each.parse() {
+                        Ok(each) if field.each.is_none() => field.each = Some(each),
+                        Ok(_) => errors.push(meta.error("duplicate `each` attribute")),
+                        Err(err) => errors.push(err),
+                    }
+                }
+            } else if meta.path.is_ident("default") {
+                if meta.input.peek(Token![=]) {
+                    let value: Expr = meta.value()?.parse()?;
+                    errors.push(Error::new_spanned(value, "`default` does not take a value"));
+                } else if field.default {
+                    errors.push(meta.error("duplicate `default` attribute"));
+                } else {
+                    field.default = true;
+                }
+            } else {
+                errors.push(unknown_key(&meta, FIELD_KEYS));
+                skip_value(&meta)?;
+            }
+            Ok(())
+        });
+
+        if let Err(err) = result {
+            errors.push(err);
+        }
+    }
+
+    field
+}
+
+// Removes #[builder(...)] attributes, which are inert helpers of the derive but
+// would not be recognized by the compiler in other positions such as function
+// parameters.
+pub fn strip(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !attr.path().is_ident("builder"));
+}
+
+// Parses `= "..."`, reporting a value of any other type without aborting the
+// rest of the attribute.
+fn string_value(meta: &ParseNestedMeta, errors: &mut Errors) -> Result<Option<LitStr>> {
+    let value: Expr = meta.value()?.parse()?;
+    match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(Some(lit)),
+        value => {
+            let key = meta.path.get_ident().unwrap();
+            let msg = format!("expected string literal as the value of `{}`", key);
+            errors.push(Error::new_spanned(value, msg));
+            Ok(None)
+        }
+    }
+}
+
+fn skip_value(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in meta.input);
+        content.parse::<TokenStream>()?;
+    }
+    Ok(())
+}
+
+fn unknown_key(meta: &ParseNestedMeta, expected: &[&str]) -> Error {
+    let key = match meta.path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => meta.path.to_token_stream().to_string().replace(' ', ""),
+    };
+    let msg = match closest(&key, expected) {
+        Some(suggestion) => format!("unknown key `{}`, did you mean `{}`?", key, suggestion),
+        None => {
+            let expected: Vec<String> = expected.iter().map(|key| format!("`{}`", key)).collect();
+            format!(
+                "unknown key `{}`, expected one of {}",
+                key,
+                expected.join(", ")
+            )
+        }
+    };
+    Error::new_spanned(&meta.path, msg)
+}
+
+// The candidate within a small edit distance of `key`, if any.
+fn closest<'a>(key: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let threshold = key.chars().count().max(3) / 3;
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// Levenshtein distance.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            let insert = curr[j] + 1;
+            let delete = prev[j + 1] + 1;
+            curr.push(substitute.min(insert).min(delete));
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
@@ -0,0 +1,328 @@
+use crate::attr::{self, Errors};
+use crate::ty;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields, Generics, Ident, Path, Result, Type, Visibility};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            Fields::Unnamed(_) | Fields::Unit => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "derive(Builder) requires a struct with named fields",
+                ));
+            }
+        },
+        Data::Enum(_) | Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "derive(Builder) is only supported for structs",
+            ));
+        }
+    };
+
+    let mut errors = Errors::default();
+    let container = attr::container(&input.attrs, &mut errors);
+    let fields: Vec<Field> = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let attrs = attr::field(&field.attrs, &mut errors);
+            Field::new(ident, &field.ty, attrs, &mut errors)
+        })
+        .collect();
+
+    let builder = Builder {
+        vis: &input.vis,
+        ident: format_ident!("{}Builder", input.ident),
+        generics: &input.generics,
+        fields,
+        phantom: None,
+        krate: container.krate,
+    };
+
+    let ident = &input.ident;
+    let builder_ident = &builder.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let builder_struct = builder.struct_def();
+    let init = builder.init();
+    let setters = builder.setters();
+    let finish = builder.finish();
+    let field_names = builder.fields.iter().map(|field| field.ident);
+    let krate = &builder.krate;
+    let errors = errors.into_compile_error();
+    let with_methods = container.with_methods.map(|_| {
+        let methods = builder.with_methods();
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #methods
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub fn builder() -> #builder_ident #ty_generics {
+                #init
+            }
+        }
+
+        #builder_struct
+
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
+            #setters
+
+            pub fn build(&mut self) -> ::std::result::Result<#ident #ty_generics, #krate::UninitializedFieldError> {
+                #finish
+                ::std::result::Result::Ok(#ident {
+                    #(#field_names,)*
+                })
+            }
+        }
+
+        #with_methods
+
+        #errors
+    })
+}
+
+pub struct Builder<'a> {
+    pub vis: &'a Visibility,
+    pub ident: Ident,
+    pub generics: &'a Generics,
+    pub fields: Vec<Field<'a>>,
+    // Type of a PhantomData field for generic parameters that might not be
+    // mentioned by any of the fields.
+    pub phantom: Option<TokenStream>,
+    // Path through which generated code refers to the derive_builder crate.
+    pub krate: Path,
+}
+
+pub struct Field<'a> {
+    pub ident: &'a Ident,
+    pub ty: &'a Type,
+    kind: Kind<'a>,
+}
+
+enum Kind<'a> {
+    Required,
+    Optional(&'a Type),
+    Default,
+    Repeated(Ident, &'a Type),
+}
+
+impl<'a> Field<'a> {
+    // An `each` on a field that is not a Vec is reported and then ignored.
+    pub fn new(ident: &'a Ident, ty: &'a Type, attrs: attr::Field, errors: &mut Errors) -> Self {
+        let elem = attrs.each.and_then(|each| match ty::vec_inner(ty) {
+            Some(elem) => Some((each, elem)),
+            None => {
+                errors.push(Error::new_spanned(
+                    ty,
+                    "`builder(each = \"...\")` requires a field of type Vec<T>",
+                ));
+                None
+            }
+        });
+        let kind = if let Some((each, elem)) = elem {
+            Kind::Repeated(each, elem)
+        } else if attrs.default {
+            Kind::Default
+        } else if let Some(inner) = ty::option_inner(ty) {
+            Kind::Optional(inner)
+        } else {
+            Kind::Required
+        };
+        Field { ident, ty, kind }
+    }
+}
+
+impl<'a> Builder<'a> {
+    pub fn struct_def(&self) -> TokenStream {
+        let vis = self.vis;
+        let ident = &self.ident;
+        let generics = self.generics;
+        let where_clause = &self.generics.where_clause;
+
+        let fields = self.fields.iter().map(|field| {
+            let ident = field.ident;
+            let storage = match &field.kind {
+                Kind::Required | Kind::Default => {
+                    let ty = field.ty;
+                    quote!(::std::option::Option<#ty>)
+                }
+                Kind::Optional(inner) => quote!(::std::option::Option<#inner>),
+                Kind::Repeated(..) => {
+                    let ty = field.ty;
+                    quote!(#ty)
+                }
+            };
+            quote!(#ident: #storage)
+        });
+
+        let phantom = self
+            .phantom
+            .as_ref()
+            .map(|phantom| quote!(__phantom: ::std::marker::PhantomData<#phantom>,));
+
+        quote! {
+            #vis struct #ident #generics #where_clause {
+                #(#fields,)*
+                #phantom
+            }
+        }
+    }
+
+    pub fn init(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        let fields = self.fields.iter().map(|field| {
+            let ident = field.ident;
+            match &field.kind {
+                Kind::Required | Kind::Optional(_) | Kind::Default => {
+                    quote!(#ident: ::std::option::Option::None)
+                }
+                Kind::Repeated(..) => quote!(#ident: ::std::default::Default::default()),
+            }
+        });
+
+        let phantom = self
+            .phantom
+            .as_ref()
+            .map(|_| quote!(__phantom: ::std::marker::PhantomData,));
+
+        quote! {
+            #ident {
+                #(#fields,)*
+                #phantom
+            }
+        }
+    }
+
+    pub fn setters(&self) -> TokenStream {
+        let setters = self.fields.iter().map(|field| {
+            let ident = field.ident;
+            let ty = field.ty;
+            match &field.kind {
+                Kind::Required | Kind::Default => quote! {
+                    pub fn #ident(&mut self, #ident: #ty) -> &mut Self {
+                        self.#ident = ::std::option::Option::Some(#ident);
+                        self
+                    }
+                },
+                Kind::Optional(inner) => quote! {
+                    pub fn #ident(&mut self, #ident: #inner) -> &mut Self {
+                        self.#ident = ::std::option::Option::Some(#ident);
+                        self
+                    }
+                },
+                Kind::Repeated(each, elem) => {
+                    let one_at_a_time = quote! {
+                        pub fn #each(&mut self, #each: #elem) -> &mut Self {
+                            self.#ident.push(#each);
+                            self
+                        }
+                    };
+                    let all_at_once = if each == ident {
+                        None
+                    } else {
+                        Some(quote! {
+                            pub fn #ident(&mut self, #ident: #ty) -> &mut Self {
+                                self.#ident = #ident;
+                                self
+                            }
+                        })
+                    };
+                    quote! {
+                        #one_at_a_time
+                        #all_at_once
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #(#setters)*
+        }
+    }
+
+    // Consuming `with_*` methods that return a modified copy of an already
+    // built value, for #[builder(with_methods)].
+    pub fn with_methods(&self) -> TokenStream {
+        let methods = self.fields.iter().map(|field| {
+            let ident = field.ident;
+            let ty = field.ty;
+            let with_ident = format_ident!("with_{}", ident);
+            let all_at_once = quote! {
+                pub fn #with_ident(mut self, #ident: #ty) -> Self {
+                    self.#ident = #ident;
+                    self
+                }
+            };
+            match &field.kind {
+                Kind::Required | Kind::Optional(_) | Kind::Default => all_at_once,
+                Kind::Repeated(each, elem) => {
+                    let with_each = format_ident!("with_{}", each);
+                    let one_at_a_time = quote! {
+                        pub fn #with_each(mut self, #each: #elem) -> Self {
+                            self.#ident.push(#each);
+                            self
+                        }
+                    };
+                    let all_at_once = if each == ident {
+                        None
+                    } else {
+                        Some(all_at_once)
+                    };
+                    quote! {
+                        #one_at_a_time
+                        #all_at_once
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #(#methods)*
+        }
+    }
+
+    // Moves every field out of the builder into a local variable of the same
+    // name, or returns early with an error if a required field was never set.
+    // Nothing is taken until all required fields are known to be present, so
+    // a failed attempt leaves the builder as it was.
+    pub fn finish(&self) -> TokenStream {
+        let krate = &self.krate;
+        let checks = self.fields.iter().filter_map(|field| {
+            let ident = field.ident;
+            let Kind::Required = field.kind else {
+                return None;
+            };
+            let name = ident.unraw().to_string();
+            Some(quote! {
+                if self.#ident.is_none() {
+                    return ::std::result::Result::Err(#krate::UninitializedFieldError::new(#name));
+                }
+            })
+        });
+
+        let takes = self.fields.iter().map(|field| {
+            let ident = field.ident;
+            let value = match &field.kind {
+                Kind::Required => quote!(self.#ident.take().unwrap()),
+                Kind::Optional(_) => quote!(self.#ident.take()),
+                Kind::Default => quote!(self.#ident.take().unwrap_or_default()),
+                Kind::Repeated(..) => quote!(::std::mem::take(&mut self.#ident)),
+            };
+            quote!(let #ident = #value;)
+        });
+
+        quote! {
+            #(#checks)*
+            #(#takes)*
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::attr::{self, Container, Errors};
+use crate::expand::{Builder, Field};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::Parser;
+use syn::{Error, FnArg, GenericParam, Ident, ItemFn, Pat, Result, ReturnType, Safety};
+
+// Turns
+//
+//     fn spawn(exe: String, #[builder(default)] nice: i32) -> Child {...}
+//
+// into a function `spawn()` returning a SpawnBuilder, whose setters correspond
+// to the parameters and whose `call` method invokes the original body.
+pub fn expand(args: TokenStream, mut item: ItemFn) -> Result<TokenStream> {
+    if let Some(asyncness) = &item.sig.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "#[builder] does not support async fn",
+        ));
+    }
+    if let Some(variadic) = &item.sig.variadic {
+        return Err(Error::new_spanned(
+            variadic,
+            "#[builder] does not support variadic functions",
+        ));
+    }
+
+    let mut errors = Errors::default();
+    let mut container = Container::default();
+    let parser = syn::meta::parser(|meta| container.parse_meta(meta, &mut errors));
+    if let Err(err) = parser.parse2(args) {
+        errors.push(err);
+    }
+    if let Some(with_methods) = &container.with_methods {
+        let msg = "`with_methods` is only supported by derive(Builder)";
+        errors.push(Error::new_spanned(with_methods, msg));
+    }
+
+    // Parameters that are not plain identifiers leave no name to give their
+    // setter, so no builder is generated for them, but the attributes of every
+    // parameter are still checked so that all errors are reported together.
+    let mut plain = true;
+    let mut field_attrs = Vec::new();
+    for arg in &mut item.sig.inputs {
+        let arg = match arg {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(receiver) => {
+                errors.push(Error::new_spanned(
+                    receiver,
+                    "#[builder] is not supported on methods",
+                ));
+                plain = false;
+                continue;
+            }
+        };
+        match &*arg.pat {
+            Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {}
+            pat => {
+                errors.push(Error::new_spanned(
+                    pat,
+                    "#[builder] requires every parameter to be a plain identifier",
+                ));
+                plain = false;
+            }
+        }
+        field_attrs.push(attr::field(&arg.attrs, &mut errors));
+        attr::strip(&mut arg.attrs);
+    }
+    if !plain {
+        return Err(errors.into_error().unwrap());
+    }
+
+    let mut fields = Vec::new();
+    for (arg, attrs) in item.sig.inputs.iter().zip(field_attrs) {
+        let FnArg::Typed(arg) = arg else {
+            unreachable!();
+        };
+        let Pat::Ident(pat) = &*arg.pat else {
+            unreachable!();
+        };
+        fields.push(Field::new(&pat.ident, &arg.ty, attrs, &mut errors));
+    }
+
+    let generics = &item.sig.generics;
+    let phantom = if generics.params.is_empty() {
+        None
+    } else {
+        let params = generics.params.iter().map(|param| match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(&#lifetime ())
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote!([(); #ident])
+            }
+        });
+        Some(quote!(fn() -> (#(#params,)*)))
+    };
+
+    let builder = Builder {
+        vis: &item.vis,
+        ident: format_ident!("{}Builder", camel_case(&item.sig.ident)),
+        generics,
+        fields,
+        phantom,
+        krate: container.krate,
+    };
+
+    let attrs = &item.attrs;
+    let vis = &item.vis;
+    let ident = &item.sig.ident;
+    let builder_ident = &builder.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let builder_struct = builder.struct_def();
+    let init = builder.init();
+    let setters = builder.setters();
+    let finish = builder.finish();
+    let args = builder.fields.iter().map(|field| field.ident);
+    let krate = &builder.krate;
+    let safety = &item.sig.safety;
+    let output = match &item.sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => quote!(#ty),
+    };
+
+    // Late-bound lifetimes cannot be named in a turbofish, so only pass the
+    // type and const parameters explicitly.
+    let turbofish_params: Vec<_> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(_) => None,
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+        })
+        .collect();
+    let turbofish = if turbofish_params.is_empty() {
+        None
+    } else {
+        Some(quote!(::<#(#turbofish_params),*>))
+    };
+
+    let mut call = quote!(#ident #turbofish (#(#args),*));
+    if let Safety::Unsafe(unsafe_token) = safety {
+        call = quote!(#unsafe_token { #call });
+    }
+
+    let sig = &item.sig;
+    let block = &item.block;
+    let errors = errors.into_compile_error();
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #ident #impl_generics () -> #builder_ident #ty_generics #where_clause {
+            #init
+        }
+
+        #builder_struct
+
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
+            #setters
+
+            pub #safety fn call(&mut self) -> ::std::result::Result<#output, #krate::UninitializedFieldError> {
+                #sig #block
+
+                #finish
+                ::std::result::Result::Ok(#call)
+            }
+        }
+
+        #errors
+    })
+}
+
+// spawn_child -> SpawnChild
+fn camel_case(ident: &Ident) -> String {
+    let mut camel = String::new();
+    for word in ident.unraw().to_string().split('_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
@@ -0,0 +1,36 @@
+use syn::{GenericArgument, PathArguments, Type};
+
+// Option<T> -> Some(T)
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    generic_inner(ty, "Option")
+}
+
+// Vec<T> -> Some(T)
+pub fn vec_inner(ty: &Type) -> Option<&Type> {
+    generic_inner(ty, "Vec")
+}
+
+// Only looks at how the type is literally written. A macro has no way to tell
+// that some other path or alias refers to the same type.
+fn generic_inner<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    if ty.qself.is_some() {
+        return None;
+    }
+    let segment = ty.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(bracketed) = &segment.arguments else {
+        return None;
+    };
+    if bracketed.args.len() != 1 {
+        return None;
+    }
+    match &bracketed.args[0] {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
@@ -1,8 +1,37 @@
-use proc_macro::TokenStream;
+// A crate with the "proc-macro" crate type can only export procedural macros,
+// so the macros live in derive_builder_impl and are re-exported here alongside
+// the types that their generated code refers to at runtime.
+//
+// Generated code names these types through `::derive_builder` by default. A
+// crate that re-exports derive_builder from elsewhere can point the macros at
+// its own path using #[builder(crate = "...")].
+pub use derive_builder_impl::{builder, Builder};
 
-#[proc_macro_derive(Builder)]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let _ = input;
+use std::error::Error;
+use std::fmt::{self, Display};
 
-    unimplemented!()
+/// Error returned by a builder when a required field was never set.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UninitializedFieldError {
+    field: &'static str,
 }
+
+impl UninitializedFieldError {
+    #[doc(hidden)]
+    pub fn new(field: &'static str) -> Self {
+        UninitializedFieldError { field }
+    }
+
+    /// Name of the field that was not set.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+}
+
+impl Display for UninitializedFieldError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "field `{}` is not set", self.field)
+    }
+}
+
+impl Error for UninitializedFieldError {}
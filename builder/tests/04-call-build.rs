@@ -1,15 +1,20 @@
 // Generate a `build` method to go from builder to original struct.
 //
 // This method should require that every one of the fields has been explicitly
-// set; it should return an error if a field is missing. The precise error type
-// is not important. Consider using Box<dyn Error>, which you can construct
-// using the impl From<String> for Box<dyn Error>.
+// set; it should return an error if a field is missing. The error is
+// derive_builder::UninitializedFieldError, which names the missing field and
+// implements std::error::Error, so it still converts into Box<dyn Error> with
+// `?`.
 //
 //     impl CommandBuilder {
-//         pub fn build(&mut self) -> Result<Command, Box<dyn Error>> {
+//         pub fn build(&mut self) -> Result<Command, UninitializedFieldError> {
 //             ...
 //         }
 //     }
+//
+// Returning a concrete type rather than Box<dyn Error> is why the generated
+// code needs a runtime crate to refer to, and so why #[builder(crate = "...")]
+// exists; see 13-crate-path.rs.
 
 use derive_builder::Builder;
 
@@ -30,4 +35,8 @@ fn main() {
 
     let command = builder.build().unwrap();
     assert_eq!(command.executable, "cargo");
+
+    let err = Command::builder().build().err().unwrap();
+    assert_eq!(err.field(), "executable");
+    let _: Box<dyn std::error::Error> = Box::new(err);
 }
@@ -0,0 +1,34 @@
+// Fields marked #[builder(default)] may be left unset, in which case the built
+// struct receives Default::default() for that field. Unlike an Option field,
+// the setter takes the field's own type and the field keeps its non-optional
+// type in the built struct.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(default)]
+    nice: i32,
+    #[builder(default)]
+    env: Vec<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.nice, 0);
+    assert!(command.env.is_empty());
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .nice(5)
+        .build()
+        .unwrap();
+    assert_eq!(command.nice, 5);
+
+    let err = Command::builder().nice(5).build().err().unwrap();
+    assert_eq!(err.to_string(), "field `executable` is not set");
+}
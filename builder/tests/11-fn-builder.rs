@@ -0,0 +1,67 @@
+// The same builder can be generated for the parameters of a function by placing
+// #[derive_builder::builder] on it. The function is replaced by one taking no
+// arguments and returning the builder, and the original body runs when the
+// builder's `call` method is invoked.
+//
+//     fn spawn() -> SpawnBuilder {...}
+//
+//     impl SpawnBuilder {
+//         pub fn exe(&mut self, exe: String) -> &mut Self {...}
+//         ...
+//         pub fn call(&mut self) -> Result<Child, UninitializedFieldError> {...}
+//     }
+//
+// Parameters follow the same rules as struct fields: Option parameters are
+// optional, #[builder(each = "...")] parameters accumulate one element at a
+// time, and #[builder(default)] parameters fall back to Default::default().
+
+#[derive(Debug, PartialEq)]
+pub struct Child {
+    exe: String,
+    args: Vec<String>,
+    nice: i32,
+    current_dir: Option<String>,
+}
+
+#[derive_builder::builder]
+pub fn spawn(
+    exe: String,
+    #[builder(each = "arg")] args: Vec<String>,
+    #[builder(default)] nice: i32,
+    current_dir: Option<String>,
+) -> Child {
+    Child {
+        exe,
+        args,
+        nice,
+        current_dir,
+    }
+}
+
+#[derive_builder::builder]
+fn sum<T: std::iter::Sum<T>>(#[builder(each = "term")] terms: Vec<T>) -> T {
+    terms.into_iter().sum()
+}
+
+fn main() {
+    let child = spawn()
+        .exe("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .nice(5)
+        .call()
+        .unwrap();
+
+    let expected = Child {
+        exe: "cargo".to_owned(),
+        args: vec!["build".to_owned(), "--release".to_owned()],
+        nice: 5,
+        current_dir: None,
+    };
+    assert_eq!(child, expected);
+
+    let err = spawn().nice(5).call().unwrap_err();
+    assert_eq!(err.to_string(), "field `exe` is not set");
+
+    assert_eq!(sum().term(1).term(2).term(3).call().unwrap(), 6);
+}
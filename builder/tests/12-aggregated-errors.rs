@@ -0,0 +1,23 @@
+// Every builder attribute on every field is checked before any error is
+// reported, so that the caller sees all of their mistakes at once instead of
+// fixing them one compile at a time.
+//
+// Misspelled keys come with a suggestion of the closest valid key, and values
+// of the wrong type are reported at the value itself.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(eac = "arg")]
+    args: Vec<String>,
+    #[builder(each = 5)]
+    env: Vec<String>,
+    #[builder(defualt, each = "dir")]
+    current_dir: Option<String>,
+    #[builder(nonsense)]
+    nice: i32,
+}
+
+fn main() {}
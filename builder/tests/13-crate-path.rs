@@ -0,0 +1,51 @@
+// Code generated by the builder refers to runtime types of the derive_builder
+// crate, such as the error returned by `build`, through the absolute path
+// `::derive_builder`. A crate that re-exports derive_builder as part of its own
+// facade can redirect those paths with #[builder(crate = "...")] so that its
+// users never need to depend on derive_builder directly.
+//
+// The attribute macro form accepts the same argument:
+//
+//     #[builder(crate = "...")]
+//     fn spawn(...) -> Child {...}
+
+mod facade {
+    pub mod builder {
+        pub use derive_builder::*;
+    }
+}
+
+use facade::builder::{builder, Builder, UninitializedFieldError};
+
+#[derive(Builder)]
+#[builder(crate = "facade::builder")]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+#[builder(crate = "crate::facade::builder")]
+fn spawn(exe: String, #[builder(default)] nice: i32) -> (String, i32) {
+    (exe, nice)
+}
+
+fn main() {
+    let err: UninitializedFieldError = Command::builder()
+        .arg("build".to_owned())
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.field(), "executable");
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+
+    assert_eq!(
+        spawn().exe("cargo".to_owned()).call().unwrap(),
+        ("cargo".to_owned(), 0)
+    );
+}
@@ -0,0 +1,53 @@
+// With #[builder(with_methods)] on the struct, the macro additionally generates
+// consuming methods on the struct itself that return a copy with one field
+// replaced. This is convenient for deriving a variation of a value that has
+// already been built.
+//
+//     impl Command {
+//         pub fn with_executable(mut self, executable: String) -> Self {...}
+//         pub fn with_args(mut self, args: Vec<String>) -> Self {...}
+//         pub fn with_arg(mut self, arg: String) -> Self {...}
+//         pub fn with_current_dir(mut self, current_dir: Option<String>) -> Self {...}
+//     }
+//
+// For fields with #[builder(each = "...")] there is also a method that appends
+// a single element, named after the `each` word.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Clone)]
+#[builder(with_methods)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+
+    let release = command
+        .clone()
+        .with_arg("--release".to_owned())
+        .with_env("RUST_BACKTRACE=1".to_owned())
+        .with_current_dir(Some("..".to_owned()));
+
+    assert_eq!(command.args, vec!["build"]);
+    assert!(command.current_dir.is_none());
+    assert_eq!(release.args, vec!["build", "--release"]);
+    assert_eq!(release.env, vec!["RUST_BACKTRACE=1"]);
+    assert_eq!(release.current_dir.as_deref(), Some(".."));
+
+    let test = release
+        .with_executable("cargo-nextest".to_owned())
+        .with_args(vec!["run".to_owned()]);
+    assert_eq!(test.executable, "cargo-nextest");
+    assert_eq!(test.args, vec!["run"]);
+}
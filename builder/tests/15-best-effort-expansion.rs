@@ -0,0 +1,30 @@
+// A mistake in a builder attribute should produce one error, at the mistake.
+// The rest of the builder is still generated as though the invalid attribute
+// were absent, so that uses of the builder elsewhere in the crate do not turn
+// into a cascade of follow-on errors and IDE completion for the builder keeps
+// working while the attribute is being fixed.
+//
+// Here the misspelled `each` leaves `args` as an ordinary Vec field with an
+// all-at-once setter, and the `each` on a non-Vec field is ignored.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(eac = "arg")]
+    args: Vec<String>,
+    #[builder(each = "dir")]
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .args(vec!["build".to_owned()])
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+
+    let _ = command;
+}
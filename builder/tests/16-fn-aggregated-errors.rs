@@ -0,0 +1,12 @@
+// The function form reports every error together too. A parameter bound by a
+// pattern has no name to give its setter, so no builder can be generated, but
+// the attributes of the other parameters are still checked.
+
+use derive_builder::builder;
+
+#[builder]
+fn connect(#[builder(eac = "x")] hosts: Vec<String>, (port, retries): (u16, u8)) {
+    let _ = (hosts, port, retries);
+}
+
+fn main() {}
@@ -0,0 +1,146 @@
+use crate::attr;
+use proc_macro2::{Ident, Span};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Error, Fields, Generics, Member, Result, Type};
+
+pub struct Input<'a> {
+    pub ident: &'a Ident,
+    pub generics: &'a Generics,
+    pub attrs: attr::Container,
+    pub data: Data<'a>,
+}
+
+pub enum Data<'a> {
+    // A struct is represented the same as a single enum variant named after
+    // the type.
+    Struct(Variant<'a>),
+    Enum(Vec<Variant<'a>>),
+}
+
+pub struct Variant<'a> {
+    pub ident: &'a Ident,
+    pub attrs: attr::Variant,
+    pub style: Style,
+    pub fields: Vec<Field<'a>>,
+}
+
+#[derive(Copy, Clone)]
+pub enum Style {
+    Struct,
+    Tuple,
+    Unit,
+}
+
+pub struct Field<'a> {
+    pub member: Member,
+    pub ty: &'a Type,
+    pub attrs: attr::Field,
+}
+
+impl<'a> Input<'a> {
+    pub fn from_syn(input: &'a DeriveInput) -> Result<Self> {
+        let data = match &input.data {
+            syn::Data::Struct(data) => {
+                let variant = Variant {
+                    ident: &input.ident,
+                    attrs: attr::Variant::default(),
+                    style: Style::from_syn(&data.fields),
+                    fields: Field::multiple_from_syn(&data.fields)?,
+                };
+                Data::Struct(variant)
+            }
+            syn::Data::Enum(data) => {
+                let variants = data
+                    .variants
+                    .iter()
+                    .map(Variant::from_syn)
+                    .collect::<Result<_>>()?;
+                Data::Enum(variants)
+            }
+            syn::Data::Union(_) => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "derive(CustomDebug) does not support unions",
+                ));
+            }
+        };
+
+        Ok(Input {
+            ident: &input.ident,
+            generics: &input.generics,
+            attrs: attr::container(&input.attrs)?,
+            data,
+        })
+    }
+
+    pub fn variants(&self) -> &[Variant<'a>] {
+        match &self.data {
+            Data::Struct(variant) => std::slice::from_ref(variant),
+            Data::Enum(variants) => variants,
+        }
+    }
+
+    pub fn variants_mut(&mut self) -> &mut [Variant<'a>] {
+        match &mut self.data {
+            Data::Struct(variant) => std::slice::from_mut(variant),
+            Data::Enum(variants) => variants,
+        }
+    }
+}
+
+impl<'a> Variant<'a> {
+    fn from_syn(variant: &'a syn::Variant) -> Result<Self> {
+        Ok(Variant {
+            ident: &variant.ident,
+            attrs: attr::variant(&variant.attrs)?,
+            style: Style::from_syn(&variant.fields),
+            fields: Field::multiple_from_syn(&variant.fields)?,
+        })
+    }
+
+    // The name printed for the variant, or for the struct.
+    pub fn name(&self) -> String {
+        match &self.attrs.rename {
+            Some(rename) => rename.value(),
+            None => self.ident.unraw().to_string(),
+        }
+    }
+}
+
+impl Style {
+    fn from_syn(fields: &Fields) -> Self {
+        match fields {
+            Fields::Named(_) => Style::Struct,
+            Fields::Unnamed(_) => Style::Tuple,
+            Fields::Unit => Style::Unit,
+        }
+    }
+}
+
+impl<'a> Field<'a> {
+    // The name printed for a named field.
+    pub fn name(&self) -> String {
+        match (&self.attrs.rename, &self.member) {
+            (Some(rename), _) => rename.value(),
+            (None, Member::Named(ident)) => ident.unraw().to_string(),
+            (None, Member::Unnamed(index)) => index.index.to_string(),
+        }
+    }
+
+    fn multiple_from_syn(fields: &'a Fields) -> Result<Vec<Self>> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                Ok(Field {
+                    member: match &field.ident {
+                        Some(ident) => Member::Named(ident.clone()),
+                        None => Member::Unnamed(i.into()),
+                    },
+                    ty: &field.ty,
+                    attrs: attr::field(&field.attrs)?,
+                })
+            })
+            .collect()
+    }
+}
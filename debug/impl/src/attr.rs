@@ -0,0 +1,373 @@
+use crate::case::RenameRule;
+use crate::template::Template;
+use proc_macro2::TokenTree;
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{
+    token, Attribute, Error, Expr, ExprLit, Ident, Lit, LitInt, LitStr, Meta, Path, Result, Token,
+    WherePredicate,
+};
+
+pub struct Container {
+    // Replaces all inferred bounds when present.
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+    // How bounds are inferred when not replaced.
+    pub infer: Infer,
+    // Fields to leave unredacted; every other field is redacted.
+    pub redact_all_except: Option<Vec<Ident>>,
+    // Path of the `transparent` key if present, for error reporting.
+    pub transparent: Option<Path>,
+    // Sort the entries of every HashMap and HashSet field.
+    pub deterministic: bool,
+    // Print `<cycle #N>` instead of recursing into a value being formatted.
+    pub cycle_safe: bool,
+    // #[debug("...")] in place of the usual struct rendering.
+    pub template: Option<Template>,
+    // Name printed for a struct in place of its own.
+    pub rename: Option<LitStr>,
+    // Applied to the fields of a struct or the variants of an enum that are
+    // not renamed individually.
+    pub rename_all: Option<RenameRule>,
+}
+
+#[derive(Default)]
+pub struct Variant {
+    pub rename: Option<LitStr>,
+    pub skip: bool,
+}
+
+pub struct Field {
+    pub format: Format,
+    pub rename: Option<LitStr>,
+    pub skip: bool,
+    // cfg predicate under which the field is skipped.
+    pub skip_if_cfg: Option<Meta>,
+    // fn(&T) -> bool deciding at runtime whether the field is skipped.
+    pub skip_if: Option<Path>,
+    pub redact: Option<Redact>,
+    // Replaces the bounds inferred from this field's type when present.
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+}
+
+#[derive(Copy, Clone)]
+pub enum Infer {
+    // #[debug(bound(infer = "params"))], the default
+    Params,
+    // #[debug(bound(infer = "fields"))]
+    Fields,
+}
+
+pub enum Redact {
+    // #[debug(redact)]
+    Full,
+    // #[debug(redact = "len")]
+    Len,
+    // #[debug(redact = "hash")]
+    Hash,
+}
+
+pub enum Format {
+    // The field's own Debug impl.
+    Debug,
+    // #[debug = "..."] or #[debug("...")]
+    String(Template),
+    // #[debug(with = "path")]
+    With(Path),
+    // #[debug(display)]
+    Display,
+    // #[debug(max_items = N)]
+    MaxItems(usize),
+    // #[debug(max_len = N)]
+    MaxLen(usize),
+    // #[debug(hex)]
+    Hex,
+    // #[debug(bin)]
+    Bin,
+    // #[debug(bytes)]
+    Bytes,
+    // #[debug(hexdump)]
+    Hexdump,
+    // #[debug(flatten)]
+    Flatten,
+    // #[debug(sorted)]
+    Sorted,
+    // #[debug(opaque)]
+    Opaque,
+}
+
+// Collects every error found while checking the input so that they can all be
+// reported together, rather than making the caller fix them one at a time.
+#[derive(Default)]
+pub struct Errors {
+    error: Option<Error>,
+}
+
+impl Errors {
+    pub fn push(&mut self, error: Error) {
+        match &mut self.error {
+            Some(existing) => existing.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    pub fn into_result(self) -> Result<()> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn container(attrs: &[Attribute]) -> Result<Container> {
+    let mut container = Container {
+        bound: None,
+        infer: Infer::Params,
+        redact_all_except: None,
+        transparent: None,
+        deterministic: false,
+        cycle_safe: false,
+        template: None,
+        rename: None,
+        rename_all: None,
+    };
+
+    for attr in attrs {
+        if !attr.path().is_ident("debug") {
+            continue;
+        }
+
+        // #[debug("...")]
+        if let Meta::List(list) = &attr.meta {
+            if list
+                .tokens
+                .clone()
+                .into_iter()
+                .next()
+                .is_some_and(is_literal)
+            {
+                let lit: LitStr = attr.parse_args()?;
+                container.template = Some(Template::parse(lit)?);
+                continue;
+            }
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bound") {
+                if meta.input.peek(token::Paren) {
+                    // #[debug(bound(infer = "..."))]
+                    return meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("infer") {
+                            let lit: LitStr = meta.value()?.parse()?;
+                            container.infer = match lit.value().as_str() {
+                                "params" => Infer::Params,
+                                "fields" => Infer::Fields,
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        lit,
+                                        "expected `infer = \"params\"` or `infer = \"fields\"`",
+                                    ));
+                                }
+                            };
+                            Ok(())
+                        } else {
+                            Err(meta.error("unsupported debug bound attribute"))
+                        }
+                    });
+                }
+                let lit: LitStr = meta.value()?.parse()?;
+                let predicates = lit.parse_with(Punctuated::parse_terminated)?;
+                container.bound = Some(predicates);
+                Ok(())
+            } else if meta.path.is_ident("redact_all_except") {
+                let mut except = Vec::new();
+                meta.parse_nested_meta(|meta| {
+                    except.push(meta.path.require_ident()?.clone());
+                    Ok(())
+                })?;
+                container.redact_all_except = Some(except);
+                Ok(())
+            } else if meta.path.is_ident("transparent") {
+                container.transparent = Some(meta.path);
+                Ok(())
+            } else if meta.path.is_ident("deterministic") {
+                container.deterministic = true;
+                Ok(())
+            } else if meta.path.is_ident("cycle_safe") {
+                container.cycle_safe = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                container.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                let lit: LitStr = meta.value()?.parse()?;
+                container.rename_all = Some(RenameRule::parse(&lit)?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported debug attribute"))
+            }
+        })?;
+    }
+
+    Ok(container)
+}
+
+pub fn variant(attrs: &[Attribute]) -> Result<Variant> {
+    let mut variant = Variant::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("debug") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                variant.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                variant.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported debug attribute"))
+            }
+        })?;
+    }
+
+    Ok(variant)
+}
+
+pub fn field(attrs: &[Attribute]) -> Result<Field> {
+    let mut field = Field {
+        format: Format::Debug,
+        rename: None,
+        skip: false,
+        skip_if_cfg: None,
+        skip_if: None,
+        redact: None,
+        bound: None,
+    };
+
+    for attr in attrs {
+        if !attr.path().is_ident("debug") {
+            continue;
+        }
+
+        match &attr.meta {
+            // #[debug = "..."]
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) => {
+                    let template = Template::parse(lit.clone())?;
+                    set_format(&mut field.format, Format::String(template), attr)?;
+                }
+                value => return Err(Error::new_spanned(value, "expected format string")),
+            },
+            // #[debug("...")]
+            Meta::List(list)
+                if list
+                    .tokens
+                    .clone()
+                    .into_iter()
+                    .next()
+                    .is_some_and(is_literal) =>
+            {
+                let lit: LitStr = attr.parse_args()?;
+                let template = Template::parse(lit)?;
+                set_format(&mut field.format, Format::String(template), attr)?;
+            }
+            // #[debug(...)]
+            Meta::List(_) => attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    field.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    field.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip_if_cfg") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    field.skip_if_cfg = Some(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip_if") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    field.skip_if = Some(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("bound") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    field.bound = Some(lit.parse_with(Punctuated::parse_terminated)?);
+                    Ok(())
+                } else if meta.path.is_ident("with") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let path = lit.parse()?;
+                    set_format(&mut field.format, Format::With(path), &meta.path)
+                } else if meta.path.is_ident("redact") {
+                    field.redact = Some(if meta.input.peek(Token![=]) {
+                        let lit: LitStr = meta.value()?.parse()?;
+                        match lit.value().as_str() {
+                            "len" => Redact::Len,
+                            "hash" => Redact::Hash,
+                            _ => {
+                                return Err(Error::new_spanned(
+                                    lit,
+                                    "expected `redact`, `redact = \"len\"` or `redact = \"hash\"`",
+                                ));
+                            }
+                        }
+                    } else {
+                        Redact::Full
+                    });
+                    Ok(())
+                } else if meta.path.is_ident("display") {
+                    set_format(&mut field.format, Format::Display, &meta.path)
+                } else if meta.path.is_ident("hex") {
+                    set_format(&mut field.format, Format::Hex, &meta.path)
+                } else if meta.path.is_ident("bin") {
+                    set_format(&mut field.format, Format::Bin, &meta.path)
+                } else if meta.path.is_ident("bytes") {
+                    set_format(&mut field.format, Format::Bytes, &meta.path)
+                } else if meta.path.is_ident("hexdump") {
+                    set_format(&mut field.format, Format::Hexdump, &meta.path)
+                } else if meta.path.is_ident("flatten") {
+                    set_format(&mut field.format, Format::Flatten, &meta.path)
+                } else if meta.path.is_ident("sorted") {
+                    set_format(&mut field.format, Format::Sorted, &meta.path)
+                } else if meta.path.is_ident("opaque") {
+                    set_format(&mut field.format, Format::Opaque, &meta.path)
+                } else if meta.path.is_ident("max_items") {
+                    let max: LitInt = meta.value()?.parse()?;
+                    let format = Format::MaxItems(max.base10_parse()?);
+                    set_format(&mut field.format, format, &meta.path)
+                } else if meta.path.is_ident("max_len") {
+                    let max: LitInt = meta.value()?.parse()?;
+                    let format = Format::MaxLen(max.base10_parse()?);
+                    set_format(&mut field.format, format, &meta.path)
+                } else {
+                    Err(meta.error("unsupported debug attribute"))
+                }
+            })?,
+            Meta::Path(path) => {
+                return Err(Error::new_spanned(
+                    path,
+                    "expected `debug = \"...\"` or `debug(...)`",
+                ));
+            }
+        }
+    }
+
+    Ok(field)
+}
+
+fn is_literal(token: TokenTree) -> bool {
+    matches!(token, TokenTree::Literal(_))
+}
+
+fn set_format(format: &mut Format, new: Format, tokens: impl ToTokens) -> Result<()> {
+    if let Format::Debug = format {
+        *format = new;
+        Ok(())
+    } else {
+        Err(Error::new_spanned(
+            tokens,
+            "conflicting debug formats for this field",
+        ))
+    }
+}
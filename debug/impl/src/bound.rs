@@ -0,0 +1,173 @@
+use crate::attr::Infer;
+use proc_macro2::{Ident, TokenStream};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{parse_quote, Generics, Type, TypeFnPtr, TypePath, TypeTraitObject, WherePredicate};
+
+#[derive(Copy, Clone)]
+pub struct Inference<'a> {
+    // Name of the type being derived, to recognize recursive fields.
+    pub ident: &'a Ident,
+    pub generics: &'a Generics,
+    pub strategy: Infer,
+}
+
+impl<'a> Inference<'a> {
+    // Infers the bounds required for a field of type `ty` to be formatted
+    // through the trait `bound`.
+    //
+    // With the default strategy, `infer = "params"`, that is:
+    //
+    //   - `T: Trait` for each type parameter that the field mentions, and
+    //   - `T::Assoc: Trait` or `<T as Other>::Assoc: Trait` for each associated
+    //     type of a type parameter that the field mentions.
+    //
+    // With `infer = "fields"` it is instead `FieldType: Trait` for each field
+    // whose type mentions any type parameter.
+    //
+    // Either way, mentions that cannot affect whether the field implements the
+    // trait are ignored: anything inside PhantomData, fn pointers and trait
+    // objects, and fields that refer back to the type being derived, whose
+    // bounds are the ones being inferred here to begin with.
+    //
+    // This is a heuristic. Without name resolution there is no way to know
+    // which trait impls the field types really rely on.
+    pub fn infer(&self, ty: &Type, bound: &TokenStream) -> Vec<WherePredicate> {
+        let params: Vec<&Ident> = self
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect();
+        let mut visitor = BoundVisitor {
+            ident: self.ident,
+            params: &params,
+            used: vec![false; params.len()],
+            associated: Vec::new(),
+            recursive: false,
+        };
+        visitor.visit_type(ty);
+
+        let mut predicates = Vec::new();
+        match self.strategy {
+            Infer::Params => {
+                for (param, used) in params.iter().zip(visitor.used) {
+                    if used {
+                        predicates.push(parse_quote!(#param: #bound));
+                    }
+                }
+                for path in visitor.associated {
+                    predicates.push(parse_quote!(#path: #bound));
+                }
+            }
+            Infer::Fields => {
+                let generic = visitor.used.contains(&true) || !visitor.associated.is_empty();
+                if generic && !visitor.recursive {
+                    predicates.push(parse_quote!(#ty: #bound));
+                }
+            }
+        }
+        predicates
+    }
+}
+
+// Different fields frequently produce the same bound.
+pub fn dedup(predicates: &mut Vec<WherePredicate>) {
+    let mut seen = Vec::new();
+    predicates.retain(|predicate| {
+        let repr = predicate.to_token_stream().to_string();
+        if seen.contains(&repr) {
+            false
+        } else {
+            seen.push(repr);
+            true
+        }
+    });
+}
+
+struct BoundVisitor<'a> {
+    ident: &'a Ident,
+    params: &'a [&'a Ident],
+    used: Vec<bool>,
+    associated: Vec<&'a TypePath>,
+    recursive: bool,
+}
+
+impl<'a> BoundVisitor<'a> {
+    fn is_param(&self, ident: &Ident) -> Option<usize> {
+        self.params.iter().position(|param| *param == ident)
+    }
+}
+
+impl<'a> Visit<'a> for BoundVisitor<'a> {
+    fn visit_type_path(&mut self, ty: &'a TypePath) {
+        if let Some(qself) = &ty.qself {
+            // <T as Trait>::Assoc
+            if mentions_param(&qself.ty, self.params) {
+                self.associated.push(ty);
+                return;
+            }
+        } else if ty.path.leading_colon.is_none() {
+            let segments = &ty.path.segments;
+            let first = &segments[0].ident;
+            if let Some(i) = self.is_param(first) {
+                if segments.len() == 1 {
+                    self.used[i] = true;
+                } else {
+                    // T::Assoc
+                    self.associated.push(ty);
+                }
+                return;
+            }
+            if first == "Self" {
+                self.recursive = true;
+                return;
+            }
+        }
+
+        let last = &ty.path.segments.last().unwrap().ident;
+        if last == self.ident {
+            self.recursive = true;
+            return;
+        }
+        // PhantomData<T> is Debug regardless of T.
+        if last == "PhantomData" {
+            return;
+        }
+
+        visit::visit_type_path(self, ty);
+    }
+
+    // Function pointers are Debug regardless of their signature.
+    fn visit_type_fn_ptr(&mut self, _ty: &'a TypeFnPtr) {}
+
+    // Whether a trait object is Debug depends on its trait, not on the trait's
+    // type parameters.
+    fn visit_type_trait_object(&mut self, _ty: &'a TypeTraitObject) {}
+}
+
+fn mentions_param(ty: &Type, params: &[&Ident]) -> bool {
+    struct MentionsParam<'a> {
+        params: &'a [&'a Ident],
+        found: bool,
+    }
+
+    impl<'a, 'ast> Visit<'ast> for MentionsParam<'a> {
+        fn visit_type_path(&mut self, ty: &'ast TypePath) {
+            if ty.qself.is_none() && ty.path.leading_colon.is_none() {
+                let first = &ty.path.segments[0].ident;
+                if self.params.contains(&first) {
+                    self.found = true;
+                    return;
+                }
+            }
+            visit::visit_type_path(self, ty);
+        }
+    }
+
+    let mut visitor = MentionsParam {
+        params,
+        found: false,
+    };
+    visitor.visit_type(ty);
+    visitor.found
+}
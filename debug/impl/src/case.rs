@@ -0,0 +1,68 @@
+use syn::{Error, LitStr, Result};
+
+// #[debug(rename_all = "...")]
+#[derive(Copy, Clone)]
+pub enum RenameRule {
+    // "camelCase"
+    CamelCase,
+    // "SCREAMING_SNAKE_CASE"
+    ScreamingSnakeCase,
+}
+
+impl RenameRule {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "camelCase" => Ok(RenameRule::CamelCase),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnakeCase),
+            _ => Err(Error::new_spanned(
+                lit,
+                "expected `rename_all = \"camelCase\"` or `rename_all = \"SCREAMING_SNAKE_CASE\"`",
+            )),
+        }
+    }
+
+    // Field names are expected to be snake_case.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::CamelCase => {
+                let mut camel = String::new();
+                let mut capitalize = false;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = !camel.is_empty();
+                    } else if capitalize {
+                        camel.extend(ch.to_uppercase());
+                        capitalize = false;
+                    } else {
+                        camel.push(ch);
+                    }
+                }
+                camel
+            }
+            RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+        }
+    }
+
+    // Variant names are expected to be PascalCase.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::ScreamingSnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.extend(ch.to_uppercase());
+                }
+                snake
+            }
+        }
+    }
+}
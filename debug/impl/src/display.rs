@@ -0,0 +1,195 @@
+use crate::attr::{Errors, Infer};
+use crate::bound::{self, Inference};
+use crate::expand::binding;
+use crate::template::{Arg, Template};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, LitStr, Member, Result};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let inference = Inference {
+        ident: &input.ident,
+        generics: &input.generics,
+        strategy: Infer::Params,
+    };
+    let mut predicates = Vec::new();
+
+    let container = template(&input.attrs)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let Some(template) = container else {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "missing #[display(\"...\")] attribute",
+                ));
+            };
+            let (pat, args) = arm(
+                quote!(Self),
+                &data.fields,
+                &template,
+                &inference,
+                &mut predicates,
+            )?;
+            quote! {
+                match self {
+                    #pat => formatter.write_fmt(#args),
+                }
+            }
+        }
+        Data::Enum(data) => {
+            if let Some(template) = container {
+                return Err(Error::new_spanned(
+                    template.lit(),
+                    "put #[display(\"...\")] on each variant of an enum",
+                ));
+            }
+            if data.variants.is_empty() {
+                quote!(match *self {})
+            } else {
+                let mut arms = Vec::new();
+                for variant in &data.variants {
+                    let Some(template) = template(&variant.attrs)? else {
+                        return Err(Error::new_spanned(
+                            &variant.ident,
+                            "missing #[display(\"...\")] attribute on this variant",
+                        ));
+                    };
+                    let ident = &variant.ident;
+                    let path = quote!(Self::#ident);
+                    let (pat, args) = arm(
+                        path,
+                        &variant.fields,
+                        &template,
+                        &inference,
+                        &mut predicates,
+                    )?;
+                    arms.push(quote!(#pat => formatter.write_fmt(#args),));
+                }
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "derive(CustomDisplay) does not support unions",
+            ));
+        }
+    };
+
+    bound::dedup(&mut predicates);
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                #body
+            }
+        }
+    })
+}
+
+fn template(attrs: &[Attribute]) -> Result<Option<Template>> {
+    let mut template = None;
+    for attr in attrs {
+        if !attr.path().is_ident("display") {
+            continue;
+        }
+        if template.is_some() {
+            return Err(Error::new_spanned(attr, "duplicate #[display] attribute"));
+        }
+        let lit: LitStr = attr.parse_args()?;
+        template = Some(Template::parse(lit)?);
+    }
+    Ok(template)
+}
+
+// The pattern and fmt::Arguments expression of a match arm that formats the
+// template, binding only the fields that the template refers to.
+fn arm(
+    path: TokenStream,
+    fields: &Fields,
+    template: &Template,
+    inference: &Inference,
+    predicates: &mut Vec<syn::WherePredicate>,
+) -> Result<(TokenStream, TokenStream)> {
+    let members: Vec<Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        })
+        .collect();
+    let position = |arg: &Arg| {
+        members.iter().position(|member| match (member, arg) {
+            (Member::Named(ident), Arg::Named(name)) => ident == name,
+            (Member::Unnamed(index), Arg::Index(i)) => index.index as usize == *i,
+            _ => false,
+        })
+    };
+
+    let mut errors = Errors::default();
+    let mut used = vec![false; members.len()];
+    for (arg, location) in template.args() {
+        match position(arg) {
+            Some(i) => used[i] = true,
+            None => {
+                let msg = match arg {
+                    Arg::Value => {
+                        "format placeholders must name a field, as in `{field}`".to_owned()
+                    }
+                    Arg::Named(name) => format!("no field named `{}`", name),
+                    Arg::Index(i) => format!("no field at index {}", i),
+                };
+                errors.push(template.error(&location, msg));
+            }
+        }
+    }
+    errors.into_result()?;
+
+    let fields: Vec<_> = fields.iter().collect();
+    for placeholder in template.placeholders() {
+        let field = fields[position(&placeholder.arg).unwrap()];
+        let format_trait = placeholder.format_trait().unwrap();
+        predicates.extend(inference.infer(&field.ty, &format_trait));
+    }
+
+    let pat = pattern(path, &members, &used);
+    let args = template.format_args(|arg| {
+        let binding = binding(position(arg).unwrap());
+        (binding.clone(), quote!(#binding))
+    });
+    Ok((pat, args))
+}
+
+// Pattern that binds by reference only the fields marked as used. Also used
+// for container-level templates in derive(CustomDebug) and derive(Inspect).
+pub fn pattern(path: TokenStream, members: &[Member], used: &[bool]) -> TokenStream {
+    match members.first() {
+        Some(Member::Named(_)) => {
+            let used: Vec<usize> = (0..members.len()).filter(|&i| used[i]).collect();
+            let members = used.iter().map(|&i| &members[i]);
+            let bindings = used.iter().map(|&i| binding(i));
+            quote!(#path { #(#members: #bindings,)* .. })
+        }
+        Some(Member::Unnamed(_)) => {
+            let bindings = used.iter().enumerate().map(|(i, used)| {
+                if *used {
+                    let binding = binding(i);
+                    quote!(#binding)
+                } else {
+                    quote!(_)
+                }
+            });
+            quote!(#path(#(#bindings),*))
+        }
+        None => quote!(#path { .. }),
+    }
+}
@@ -0,0 +1,861 @@
+use crate::ast::{Data, Field, Input, Style, Variant};
+use crate::attr::{Errors, Format, Infer, Redact};
+use crate::bound;
+use crate::display;
+use crate::opaque;
+use crate::template::{Arg, Template};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Error, LitStr, Member, Result, Type, WherePredicate};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let input = prepare(input)?;
+
+    let body = body(&input, &Debug);
+    let body = if input.attrs.cycle_safe {
+        quote! {
+            ::derive_debug::__private::cycle_guard(self, formatter, |formatter| #body)
+        }
+    } else {
+        body
+    };
+
+    let mut generics = input.generics.clone();
+    let predicates = predicates(&input, &quote!(::derive_debug::DebugFields));
+    generics.make_where_clause().predicates.extend(predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = input.ident;
+
+    // Structs with named fields can be flattened into other structs, unless
+    // they are rendered by a template instead of field by field.
+    let debug_fields = match &input.data {
+        Data::Struct(_) if input.attrs.template.is_some() => None,
+        Data::Struct(variant) if matches!(variant.style, Style::Struct) => {
+            let pat = pattern(quote!(Self), variant);
+            let fields = write_fields(variant);
+            Some(quote! {
+                impl #impl_generics ::derive_debug::DebugFields for #ident #ty_generics #where_clause {
+                    fn fmt_fields(&self, builder: &mut ::std::fmt::DebugStruct<'_, '_>) -> bool {
+                        match self {
+                            #pat => #fields,
+                        }
+                    }
+                }
+            })
+        }
+        _ => None,
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                #body
+            }
+        }
+
+        #debug_fields
+    })
+}
+
+// What derive(CustomDebug) and derive(Inspect) each produce for the parts of
+// the value. Both match on the value the same way, in `body`.
+pub trait Render {
+    // A struct or enum variant, with its fields bound as by `pattern`.
+    fn variant(&self, variant: &Variant) -> TokenStream;
+    // A #[debug(transparent)] struct, as its one field.
+    fn transparent(&self, variant: &Variant) -> TokenStream;
+    // A struct with a container template, given its fmt::Arguments.
+    fn template(&self, args: TokenStream) -> TokenStream;
+}
+
+struct Debug;
+
+impl Render for Debug {
+    fn variant(&self, variant: &Variant) -> TokenStream {
+        fmt_variant(variant)
+    }
+
+    fn transparent(&self, variant: &Variant) -> TokenStream {
+        let value = field_value(variant, 0);
+        quote!(::std::fmt::Debug::fmt(#value, formatter))
+    }
+
+    fn template(&self, args: TokenStream) -> TokenStream {
+        quote!(formatter.write_fmt(#args))
+    }
+}
+
+// Match on `self` that renders whichever variant the value is.
+pub fn body(input: &Input, render: &impl Render) -> TokenStream {
+    match &input.data {
+        Data::Struct(variant) if input.attrs.template.is_some() => {
+            let template = input.attrs.template.as_ref().unwrap();
+            let (pat, args) = template_arm(quote!(Self), variant, template);
+            let rendered = render.template(args);
+            quote! {
+                match self {
+                    #pat => #rendered,
+                }
+            }
+        }
+        Data::Struct(variant) if input.attrs.transparent.is_some() => {
+            let pat = pattern(quote!(Self), variant);
+            let rendered = render.transparent(variant);
+            quote! {
+                match self {
+                    #pat => #rendered,
+                }
+            }
+        }
+        Data::Struct(variant) => {
+            let pat = pattern(quote!(Self), variant);
+            let rendered = render.variant(variant);
+            quote! {
+                match self {
+                    #pat => #rendered,
+                }
+            }
+        }
+        Data::Enum(variants) if variants.is_empty() => quote!(match *self {}),
+        Data::Enum(variants) => {
+            let arms = variants.iter().map(|variant| {
+                let ident = variant.ident;
+                let pat = pattern(quote!(Self::#ident), variant);
+                let rendered = render.variant(variant);
+                quote!(#pat => #rendered,)
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+// Bounds of the derived impl: the container's #[debug(bound = "...")] if
+// present, and otherwise those inferred from the fields.
+pub fn predicates(input: &Input, flatten: &TokenStream) -> Vec<WherePredicate> {
+    if let Some(bound) = &input.attrs.bound {
+        return bound.iter().cloned().collect();
+    }
+    let inference = bound::Inference {
+        ident: input.ident,
+        generics: input.generics,
+        strategy: input.attrs.infer,
+    };
+    infer_bounds(input, &inference, flatten)
+}
+
+// Parses the input and applies the container attributes that rewrite field
+// attributes, shared by derive(CustomDebug) and derive(Inspect).
+pub fn prepare(input: &DeriveInput) -> Result<Input<'_>> {
+    let mut input = Input::from_syn(input)?;
+    rename(&mut input)?;
+    redact_all_except(&mut input)?;
+    deterministic(&mut input);
+    implicit_opaque(&mut input);
+    check_transparent(&input)?;
+    check_formats(&input)?;
+    check_flatten(&input)?;
+    check_template(&input)?;
+    Ok(input)
+}
+
+// Applies a container-level #[debug(rename = "...")] to the struct and
+// #[debug(rename_all = "...")] to the fields or variants not renamed on their
+// own.
+fn rename(input: &mut Input) -> Result<()> {
+    for variant in input.variants() {
+        for field in &variant.fields {
+            if let (Some(rename), Member::Unnamed(_)) = (&field.attrs.rename, &field.member) {
+                return Err(Error::new_spanned(
+                    rename,
+                    "#[debug(rename = \"...\")] is only supported on named fields",
+                ));
+            }
+        }
+    }
+
+    let rename_all = input.attrs.rename_all;
+    match &mut input.data {
+        Data::Struct(variant) => {
+            variant.attrs.rename = input.attrs.rename.take();
+            let Some(rule) = rename_all else {
+                return Ok(());
+            };
+            for field in &mut variant.fields {
+                if let (None, Member::Named(ident)) = (&field.attrs.rename, &field.member) {
+                    let name = rule.apply_to_field(&ident.unraw().to_string());
+                    field.attrs.rename = Some(LitStr::new(&name, ident.span()));
+                }
+            }
+        }
+        Data::Enum(variants) => {
+            if let Some(rename) = &input.attrs.rename {
+                return Err(Error::new_spanned(
+                    rename,
+                    "#[debug(rename = \"...\")] is not supported on enums, which print only their variant names",
+                ));
+            }
+            let Some(rule) = rename_all else {
+                return Ok(());
+            };
+            for variant in variants {
+                if variant.attrs.rename.is_none() {
+                    let name = rule.apply_to_variant(&variant.ident.unraw().to_string());
+                    variant.attrs.rename = Some(LitStr::new(&name, variant.ident.span()));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Applies a container-level #[debug(redact_all_except(...))] to the fields
+// that do not already have their own redaction mode.
+fn redact_all_except(input: &mut Input) -> Result<()> {
+    let Some(except) = input.attrs.redact_all_except.take() else {
+        return Ok(());
+    };
+
+    for name in &except {
+        let exists = input.variants().iter().any(|variant| {
+            variant
+                .fields
+                .iter()
+                .any(|field| matches!(&field.member, Member::Named(ident) if ident == name))
+        });
+        if !exists {
+            let msg = format!("no field named `{}`", name);
+            return Err(Error::new_spanned(name, msg));
+        }
+    }
+
+    for variant in input.variants_mut() {
+        for field in &mut variant.fields {
+            let excepted = match &field.member {
+                Member::Named(ident) => except.contains(ident),
+                Member::Unnamed(_) => false,
+            };
+            if field.attrs.redact.is_none() && !excepted {
+                field.attrs.redact = Some(Redact::Full);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Applies a container-level #[debug(deterministic)] to the fields whose type is
+// written as HashMap or HashSet and that are otherwise printed as they are.
+// Type aliases and other hash-based collections are out of reach without name
+// resolution, and can use #[debug(sorted)] directly.
+fn deterministic(input: &mut Input) {
+    if !input.attrs.deterministic {
+        return;
+    }
+
+    for variant in input.variants_mut() {
+        for field in &mut variant.fields {
+            let Type::Path(ty) = field.ty else {
+                continue;
+            };
+            let ident = &ty.path.segments.last().unwrap().ident;
+            let hashed = ident == "HashMap" || ident == "HashSet";
+            if hashed && matches!(field.attrs.format, Format::Debug) {
+                field.attrs.format = Format::Sorted;
+            }
+        }
+    }
+}
+
+// Fields of closure and function pointer types are opaque even without
+// #[debug(opaque)], as long as no other format is given.
+fn implicit_opaque(input: &mut Input) {
+    for variant in input.variants_mut() {
+        for field in &mut variant.fields {
+            if matches!(field.attrs.format, Format::Debug) && opaque::is_implicit(field.ty) {
+                field.attrs.format = Format::Opaque;
+            }
+        }
+    }
+}
+
+// #[debug(transparent)] forwards to the Debug impl of the one and only field.
+fn check_transparent(input: &Input) -> Result<()> {
+    let Some(transparent) = &input.attrs.transparent else {
+        return Ok(());
+    };
+
+    let msg = match &input.data {
+        Data::Struct(variant) if variant.fields.len() == 1 => {
+            if variant.fields[0].attrs.skip {
+                "#[debug(transparent)] cannot skip its only field"
+            } else {
+                return Ok(());
+            }
+        }
+        Data::Struct(_) => "#[debug(transparent)] requires a struct with exactly one field",
+        Data::Enum(_) => "#[debug(transparent)] is not supported on enums",
+    };
+    Err(Error::new_spanned(transparent, msg))
+}
+
+// Named arguments in a field's format string refer to sibling fields, as in
+// #[debug("{} {unit}")].
+fn check_formats(input: &Input) -> Result<()> {
+    let mut errors = Errors::default();
+    for variant in input.variants() {
+        for field in &variant.fields {
+            let Format::String(template) = &field.attrs.format else {
+                continue;
+            };
+            // Exactly one placeholder formats the field itself.
+            let mut own = template
+                .placeholders()
+                .filter(|placeholder| matches!(placeholder.arg, Arg::Value | Arg::Index(0)));
+            if own.next().is_none() {
+                let msg = "format string has no `{}` placeholder for the field";
+                errors.push(Error::new_spanned(template.lit(), msg));
+            }
+            for (i, extra) in own.enumerate() {
+                let location = template.location(extra);
+                let msg = format!(
+                    "format string has more than one placeholder for the field, the {} is {}",
+                    ordinal(i + 2),
+                    template.describe(&location),
+                );
+                errors.push(Error::new(template.span(&location), msg));
+            }
+            for (arg, location) in template.args() {
+                let msg = match arg {
+                    Arg::Value | Arg::Index(0) => continue,
+                    Arg::Index(_) => "only the field itself is positional".to_owned(),
+                    Arg::Named(name) => match sibling(variant, name) {
+                        None => format!("no field named `{}`", name),
+                        Some(i) if variant.fields[i].attrs.redact.is_some() => {
+                            format!("field `{}` is redacted", name)
+                        }
+                        Some(_) => continue,
+                    },
+                };
+                errors.push(template.error(&location, msg));
+            }
+        }
+    }
+    errors.into_result()
+}
+
+fn ordinal(n: usize) -> String {
+    match n {
+        2 => "second".to_owned(),
+        3 => "third".to_owned(),
+        n => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{}{}", n, suffix)
+        }
+    }
+}
+
+fn sibling(variant: &Variant, name: &Ident) -> Option<usize> {
+    variant
+        .fields
+        .iter()
+        .position(|field| matches!(&field.member, Member::Named(ident) if ident == name))
+}
+
+// Fields referenced from the format strings of the variant's printed fields.
+fn referenced(variant: &Variant) -> Vec<bool> {
+    let mut referenced = vec![false; variant.fields.len()];
+    for field in &variant.fields {
+        if field.attrs.skip || field.attrs.redact.is_some() {
+            continue;
+        }
+        let Format::String(template) = &field.attrs.format else {
+            continue;
+        };
+        for (arg, _span) in template.args() {
+            if let Arg::Named(name) = arg {
+                referenced[sibling(variant, name).unwrap()] = true;
+            }
+        }
+    }
+    referenced
+}
+
+// A container-level #[debug("...")] replaces the rendering of a struct.
+fn check_template(input: &Input) -> Result<()> {
+    let Some(template) = &input.attrs.template else {
+        return Ok(());
+    };
+
+    let msg = match &input.data {
+        Data::Struct(_) if input.attrs.transparent.is_some() => {
+            "#[debug(\"...\")] and #[debug(transparent)] cannot be combined"
+        }
+        Data::Struct(variant) => return check_template_fields(variant, template),
+        Data::Enum(_) => "#[debug(\"...\")] is not supported on enums",
+    };
+    Err(Error::new_spanned(template.lit(), msg))
+}
+
+// Fields named by a container template are formatted through their own
+// attributes, which only produce Debug output: a redacted field or one with a
+// format like #[debug(hex)] can be named by `{field:?}` but not by `{field}`.
+// Skipped fields cannot be named at all.
+fn check_template_fields(variant: &Variant, template: &Template) -> Result<()> {
+    let mut errors = Errors::default();
+
+    // Whether each argument is formatted with Debug, as opposed to formatted
+    // with another trait or used as a width or precision.
+    let placeholders = template.placeholders().map(|placeholder| {
+        let debug = placeholder.is_debug();
+        (&placeholder.arg, template.location(placeholder), debug)
+    });
+    let counts = template
+        .counts()
+        .map(|(arg, location)| (arg, location, false));
+    for (arg, location, debug) in placeholders.chain(counts) {
+        let Some(i) = template_field(variant, arg) else {
+            let msg = match arg {
+                Arg::Value => "format placeholders must name a field, as in `{field}`".to_owned(),
+                Arg::Named(name) => format!("no field named `{}`", name),
+                Arg::Index(i) => format!("no field at index {}", i),
+            };
+            errors.push(template.error(&location, msg));
+            continue;
+        };
+        let field = &variant.fields[i];
+        let name = field.name();
+        let msg = if field.attrs.skip
+            || field.attrs.skip_if.is_some()
+            || field.attrs.skip_if_cfg.is_some()
+        {
+            format!("field `{}` is skipped", name)
+        } else if debug || is_plain(field) {
+            continue;
+        } else if field.attrs.redact.is_some() {
+            format!(
+                "field `{}` is redacted, so it can only be formatted with `?`",
+                name
+            )
+        } else {
+            format!(
+                "field `{}` has its own debug format, so it can only be formatted with `?`",
+                name,
+            )
+        };
+        errors.push(template.error(&location, msg));
+    }
+
+    errors.into_result()
+}
+
+// The field that an argument of a container template names.
+fn template_field(variant: &Variant, arg: &Arg) -> Option<usize> {
+    match arg {
+        Arg::Value => None,
+        Arg::Named(name) => sibling(variant, name),
+        Arg::Index(i) => variant.fields.iter().position(
+            |field| matches!(&field.member, Member::Unnamed(index) if index.index as usize == *i),
+        ),
+    }
+}
+
+// Whether the field is formatted as it is, so that a template can use any of
+// its formatting traits.
+fn is_plain(field: &Field) -> bool {
+    field.attrs.redact.is_none() && matches!(field.attrs.format, Format::Debug | Format::Flatten)
+}
+
+// The pattern and fmt::Arguments expression of a match arm that formats a
+// container template. Fields that are not plain are formatted as they would be
+// within the struct, so `{token:?}` of a redacted field prints `<redacted>`.
+fn template_arm(
+    path: TokenStream,
+    variant: &Variant,
+    template: &Template,
+) -> (TokenStream, TokenStream) {
+    let mut used = vec![false; variant.fields.len()];
+    for (arg, _location) in template.args() {
+        let i = template_field(variant, arg).unwrap();
+        if is_plain(&variant.fields[i]) {
+            used[i] = true;
+        } else {
+            for j in value_bindings(variant, i) {
+                used[j] = true;
+            }
+        }
+    }
+
+    let members: Vec<Member> = variant
+        .fields
+        .iter()
+        .map(|field| field.member.clone())
+        .collect();
+    let pat = display::pattern(path, &members, &used);
+    let args = template.format_args(|arg| {
+        let i = template_field(variant, arg).unwrap();
+        let binding = binding(i);
+        if is_plain(&variant.fields[i]) {
+            (binding.clone(), quote!(#binding))
+        } else {
+            (binding, field_value(variant, i))
+        }
+    });
+    (pat, args)
+}
+
+// Tuple fields have no name to write into the outer struct's output under.
+fn check_flatten(input: &Input) -> Result<()> {
+    for variant in input.variants() {
+        for field in &variant.fields {
+            let flatten = matches!(field.attrs.format, Format::Flatten);
+            if flatten && matches!(field.member, Member::Unnamed(_)) {
+                return Err(Error::new_spanned(
+                    field.ty,
+                    "#[debug(flatten)] is only supported on named fields",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn binding(i: usize) -> Ident {
+    format_ident!("__field{}", i)
+}
+
+// Pattern that binds each field of the variant by reference.
+fn pattern(path: TokenStream, variant: &Variant) -> TokenStream {
+    match variant.style {
+        _ if variant.attrs.skip => match variant.style {
+            Style::Struct => quote!(#path { .. }),
+            Style::Tuple => quote!(#path(..)),
+            Style::Unit => path,
+        },
+        Style::Struct => {
+            let members = variant.fields.iter().map(|field| &field.member);
+            let bindings = field_bindings(variant);
+            quote!(#path { #(#members: #bindings),* })
+        }
+        Style::Tuple => {
+            let bindings = field_bindings(variant);
+            quote!(#path(#(#bindings),*))
+        }
+        Style::Unit => path,
+    }
+}
+
+// Fields whose value is never looked at are matched by `_` to avoid unused
+// variable warnings.
+fn field_bindings<'a>(variant: &'a Variant) -> impl Iterator<Item = TokenStream> + 'a {
+    let referenced = referenced(variant);
+    variant.fields.iter().enumerate().map(move |(i, field)| {
+        let unused = field.attrs.skip
+            || field.attrs.skip_if.is_none()
+                && match &field.attrs.redact {
+                    Some(redact) => matches!(redact, Redact::Full),
+                    None => matches!(field.attrs.format, Format::Opaque),
+                };
+        if unused && !referenced[i] {
+            quote!(_)
+        } else {
+            let binding = binding(i);
+            quote!(#binding)
+        }
+    })
+}
+
+fn fmt_variant(variant: &Variant) -> TokenStream {
+    let name = variant.name();
+
+    let builder = match variant.style {
+        Style::Struct => quote!(debug_struct),
+        Style::Tuple => quote!(debug_tuple),
+        Style::Unit => return quote!(formatter.write_str(#name)),
+    };
+
+    if variant.attrs.skip {
+        return quote!(formatter.#builder(#name).finish_non_exhaustive());
+    }
+
+    let fields = write_fields(variant);
+    quote! {{
+        let builder = &mut formatter.#builder(#name);
+        if #fields {
+            builder.finish_non_exhaustive()
+        } else {
+            builder.finish()
+        }
+    }}
+}
+
+// Block that adds the fields of the variant to `builder`, a DebugStruct or
+// DebugTuple, and evaluates to whether any fields were left out.
+fn write_fields(variant: &Variant) -> TokenStream {
+    let mut skipped = false;
+    let mut dynamic = false;
+    let mut stmts = Vec::new();
+    for (i, field) in variant.fields.iter().enumerate() {
+        if field.attrs.skip {
+            skipped = true;
+            continue;
+        }
+
+        let binding = binding(i);
+        let stmt = if field.attrs.redact.is_none() && matches!(field.attrs.format, Format::Flatten)
+        {
+            dynamic = true;
+            quote! {
+                non_exhaustive |= ::derive_debug::DebugFields::fmt_fields(#binding, builder);
+            }
+        } else {
+            let value = field_value(variant, i);
+            match &field.member {
+                Member::Named(_) => {
+                    let name = field.name();
+                    quote!(builder.field(#name, #value);)
+                }
+                Member::Unnamed(_) => quote!(builder.field(#value);),
+            }
+        };
+
+        match skip_condition(field, &binding) {
+            Some(condition) => {
+                dynamic = true;
+                stmts.push(quote! {
+                    if #condition {
+                        non_exhaustive = true;
+                    } else {
+                        #stmt
+                    }
+                });
+            }
+            None => stmts.push(stmt),
+        }
+    }
+
+    if dynamic {
+        quote!({
+            let mut non_exhaustive = #skipped;
+            #(#stmts)*
+            non_exhaustive
+        })
+    } else {
+        quote!({
+            #(#stmts)*
+            #skipped
+        })
+    }
+}
+
+// Condition under which a field is skipped depending on the build
+// configuration or on its value, if any.
+pub fn skip_condition(field: &Field, binding: &Ident) -> Option<TokenStream> {
+    let mut conditions = Vec::new();
+    if let Some(predicate) = &field.attrs.skip_if_cfg {
+        conditions.push(quote!(::std::cfg!(#predicate)));
+    }
+    if let Some(path) = &field.attrs.skip_if {
+        conditions.push(quote!(#path(#binding)));
+    }
+    if conditions.is_empty() {
+        None
+    } else {
+        Some(quote!(#(#conditions)||*))
+    }
+}
+
+// The fields whose bindings field_value(variant, i) refers to.
+fn value_bindings(variant: &Variant, i: usize) -> Vec<usize> {
+    let field = &variant.fields[i];
+    match (&field.attrs.redact, &field.attrs.format) {
+        (Some(Redact::Full), _) | (None, Format::Opaque) => Vec::new(),
+        (None, Format::String(template)) => {
+            let siblings = template.args().filter_map(|(arg, _location)| match arg {
+                Arg::Named(name) => sibling(variant, name),
+                Arg::Value | Arg::Index(_) => None,
+            });
+            std::iter::once(i).chain(siblings).collect()
+        }
+        _ => vec![i],
+    }
+}
+
+// Expression of type &dyn Debug for the i'th field of the variant, which like
+// its siblings is bound by reference to a local variable.
+pub fn field_value(variant: &Variant, i: usize) -> TokenStream {
+    let field = &variant.fields[i];
+    let binding = binding(i);
+    match (&field.attrs.redact, &field.attrs.format) {
+        (Some(Redact::Full), _) => quote!(&::derive_debug::__private::Redacted),
+        (Some(Redact::Len), _) => {
+            quote!(&::derive_debug::__private::RedactedLen(#binding.len()))
+        }
+        (Some(Redact::Hash), _) => {
+            quote!(&::derive_debug::__private::RedactedHash::new(#binding))
+        }
+        // Only named fields are flattened, and only within write_fields.
+        (None, Format::Debug | Format::Flatten) => quote!(&#binding),
+        (None, Format::String(template)) => {
+            let args = template.format_args(|arg| {
+                let binding = match arg {
+                    Arg::Named(name) => self::binding(sibling(variant, name).unwrap()),
+                    Arg::Value | Arg::Index(_) => binding.clone(),
+                };
+                (binding.clone(), quote!(#binding))
+            });
+            quote!(&#args)
+        }
+        (None, Format::With(path)) => {
+            quote!(&::derive_debug::__private::DebugWith::new(#binding, #path))
+        }
+        (None, Format::Display) => quote!(&::std::format_args!("{}", #binding)),
+        (None, Format::MaxItems(max)) => {
+            quote!(&::derive_debug::__private::MaxItems::new(#binding, #max))
+        }
+        (None, Format::MaxLen(max)) => {
+            quote!(&::derive_debug::__private::MaxLen::new(#binding, #max))
+        }
+        (None, Format::Opaque) => {
+            let ty = opaque::type_string(field.ty);
+            quote!(&::derive_debug::__private::Opaque(#ty))
+        }
+        (None, Format::Sorted) => quote!(&::derive_debug::__private::Sorted::new(#binding)),
+        (None, Format::Hex) => quote!(&::derive_debug::__private::Hex::new(#binding)),
+        (None, Format::Bin) => quote!(&::derive_debug::__private::Bin::new(#binding)),
+        (None, Format::Bytes) => quote!(&::derive_debug::__private::Bytes::new(#binding)),
+        (None, Format::Hexdump) => {
+            quote!(&::derive_debug::__private::Hexdump::new(#binding))
+        }
+    }
+}
+
+// Bounds are inferred from the fields of all variants together. A field with
+// its own #[debug(bound = "...")] contributes exactly those bounds instead.
+// An empty string is a valid list of bounds, so `bound = ""` opts the field
+// out of bounds altogether. Flattened fields need `flatten`, the trait through
+// which their fields are retrieved.
+fn infer_bounds(
+    input: &Input,
+    inference: &bound::Inference,
+    flatten: &TokenStream,
+) -> Vec<WherePredicate> {
+    let mut predicates = Vec::new();
+    if let (Data::Struct(variant), Some(template)) = (&input.data, &input.attrs.template) {
+        // Only the fields that the container template names are formatted.
+        for placeholder in template.placeholders() {
+            let i = template_field(variant, &placeholder.arg).unwrap();
+            let field = &variant.fields[i];
+            match &field.attrs.bound {
+                Some(bound) => predicates.extend(bound.iter().cloned()),
+                None if is_plain(field) => {
+                    let format_trait = placeholder.format_trait().unwrap();
+                    predicates.extend(inference.infer(field.ty, &format_trait));
+                }
+                None => field_bounds(variant, field, inference, flatten, &mut predicates),
+            }
+        }
+        bound::dedup(&mut predicates);
+        return predicates;
+    }
+
+    for variant in input.variants() {
+        if variant.attrs.skip {
+            continue;
+        }
+        for field in &variant.fields {
+            if field.attrs.skip {
+                continue;
+            }
+            if let Some(bound) = &field.attrs.bound {
+                predicates.extend(bound.iter().cloned());
+                continue;
+            }
+            field_bounds(variant, field, inference, flatten, &mut predicates);
+        }
+    }
+    bound::dedup(&mut predicates);
+    predicates
+}
+
+// Bounds required by the field's format, or by its redaction.
+fn field_bounds(
+    variant: &Variant,
+    field: &Field,
+    inference: &bound::Inference,
+    flatten: &TokenStream,
+    predicates: &mut Vec<WherePredicate>,
+) {
+    let debug = quote!(::std::fmt::Debug);
+    let display = quote!(::std::fmt::Display);
+    let hash = quote!(::std::hash::Hash);
+    let as_str = quote!(::std::convert::AsRef<str>);
+    let as_bytes = quote!(::std::convert::AsRef<[u8]>);
+    let lower_hex = quote!(::std::fmt::LowerHex + ::derive_debug::__private::Width);
+    let binary = quote!(::std::fmt::Binary + ::derive_debug::__private::Width);
+    let debug_sorted = quote!(::derive_debug::__private::DebugSorted);
+
+    let bound = match (&field.attrs.redact, &field.attrs.format) {
+        (Some(Redact::Full | Redact::Len), _) => return,
+        (Some(Redact::Hash), _) => &hash,
+        (None, Format::Debug) => &debug,
+        // What max_items needs is that the items are Debug, which for a field
+        // of type Vec<T> means T: Debug whatever the container's strategy,
+        // since `Vec<T>: Debug` would not say anything about its items.
+        (None, Format::MaxItems(_)) => {
+            let inference = bound::Inference {
+                strategy: Infer::Params,
+                ..*inference
+            };
+            predicates.extend(inference.infer(field.ty, &debug));
+            return;
+        }
+        (None, Format::MaxLen(_)) => &as_str,
+        (None, Format::Hex) => &lower_hex,
+        (None, Format::Bin) => &binary,
+        (None, Format::Bytes | Format::Hexdump) => &as_bytes,
+        (None, Format::Display) => &display,
+        // Each placeholder requires its own formatting trait, of either this
+        // field or the sibling that it names.
+        (None, Format::String(template)) => {
+            for placeholder in template.placeholders() {
+                let ty = match &placeholder.arg {
+                    Arg::Named(name) => variant.fields[sibling(variant, name).unwrap()].ty,
+                    Arg::Value | Arg::Index(_) => field.ty,
+                };
+                let format_trait = placeholder.format_trait().unwrap();
+                predicates.extend(inference.infer(ty, &format_trait));
+            }
+            return;
+        }
+        // The field's type as a whole, since what these traits require of its
+        // type parameters depends on that type.
+        (None, Format::Flatten | Format::Sorted) => {
+            let inference = bound::Inference {
+                strategy: Infer::Fields,
+                ..*inference
+            };
+            let bound = match field.attrs.format {
+                Format::Flatten => flatten,
+                _ => &debug_sorted,
+            };
+            predicates.extend(inference.infer(field.ty, bound));
+            return;
+        }
+        // The function's signature determines what it requires.
+        (None, Format::With(_)) => return,
+        (None, Format::Opaque) => return,
+    };
+    predicates.extend(inference.infer(field.ty, bound));
+}
@@ -0,0 +1,158 @@
+use crate::ast::{Style, Variant};
+use crate::attr::{Format, Redact};
+use crate::expand::{self, binding, Render};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Member, Result};
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream> {
+    let input = expand::prepare(input)?;
+
+    let body = expand::body(&input, &Inspect);
+
+    let mut generics = input.generics.clone();
+    let predicates = expand::predicates(&input, &quote!(::derive_debug::Inspect));
+    generics.make_where_clause().predicates.extend(predicates);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = input.ident;
+
+    Ok(quote! {
+        impl #impl_generics ::derive_debug::Inspect for #ident #ty_generics #where_clause {
+            fn inspect(&self) -> ::derive_debug::Node {
+                #body
+            }
+        }
+    })
+}
+
+struct Inspect;
+
+impl Render for Inspect {
+    fn variant(&self, variant: &Variant) -> TokenStream {
+        variant_node(variant)
+    }
+
+    fn transparent(&self, variant: &Variant) -> TokenStream {
+        field_node(variant, 0)
+    }
+
+    fn template(&self, args: TokenStream) -> TokenStream {
+        quote!(::derive_debug::Node::Leaf(::std::fmt::format(#args)))
+    }
+}
+
+// Expression of type Node for the variant, following the same rules as its
+// Debug representation.
+fn variant_node(variant: &Variant) -> TokenStream {
+    let name = variant.name();
+
+    let kind = match variant.style {
+        Style::Struct => quote!(Struct),
+        Style::Tuple => quote!(Tuple),
+        Style::Unit => {
+            return quote!(::derive_debug::Node::Leaf(::std::string::String::from(#name)))
+        }
+    };
+
+    if variant.attrs.skip {
+        return quote! {
+            ::derive_debug::Node::#kind {
+                name: ::std::string::String::from(#name),
+                fields: ::std::vec::Vec::new(),
+                non_exhaustive: true,
+            }
+        };
+    }
+
+    let mut skipped = false;
+    let mut dynamic = false;
+    let mut stmts = Vec::new();
+    for (i, field) in variant.fields.iter().enumerate() {
+        if field.attrs.skip {
+            skipped = true;
+            continue;
+        }
+
+        let binding = binding(i);
+        let stmt = match &field.member {
+            Member::Named(_) => {
+                let name = field.name();
+                if field.attrs.redact.is_none() && matches!(field.attrs.format, Format::Flatten) {
+                    dynamic = true;
+                    quote! {
+                        match ::derive_debug::Inspect::inspect(#binding) {
+                            ::derive_debug::Node::Struct {
+                                fields: flattened,
+                                non_exhaustive: skipped,
+                                ..
+                            } => {
+                                fields.extend(flattened);
+                                non_exhaustive |= skipped;
+                            }
+                            node => fields.push((::std::string::String::from(#name), node)),
+                        }
+                    }
+                } else {
+                    let node = field_node(variant, i);
+                    quote!(fields.push((::std::string::String::from(#name), #node));)
+                }
+            }
+            Member::Unnamed(_) => {
+                let node = field_node(variant, i);
+                quote!(fields.push(#node);)
+            }
+        };
+
+        match expand::skip_condition(field, &binding) {
+            Some(condition) => {
+                dynamic = true;
+                stmts.push(quote! {
+                    if #condition {
+                        non_exhaustive = true;
+                    } else {
+                        #stmt
+                    }
+                });
+            }
+            None => stmts.push(stmt),
+        }
+    }
+
+    let non_exhaustive = if dynamic {
+        quote!(let mut non_exhaustive = #skipped;)
+    } else {
+        quote!(let non_exhaustive = #skipped;)
+    };
+
+    quote!({
+        let mut fields = ::std::vec::Vec::new();
+        #non_exhaustive
+        #(#stmts)*
+        ::derive_debug::Node::#kind {
+            name: ::std::string::String::from(#name),
+            fields,
+            non_exhaustive,
+        }
+    })
+}
+
+// Expression of type Node for the i'th field of the variant. Fields printed
+// as they are become nested nodes if their type implements Inspect, and any
+// other field is a leaf holding the same text that Debug would print.
+fn field_node(variant: &Variant, i: usize) -> TokenStream {
+    let field = &variant.fields[i];
+    match (&field.attrs.redact, &field.attrs.format) {
+        (Some(Redact::Full), _) => quote!(::derive_debug::Node::Redacted),
+        (None, Format::Debug) => {
+            let binding = binding(i);
+            quote!({
+                use ::derive_debug::__private::{ViaDebug as _, ViaInspect as _};
+                (&::derive_debug::__private::InspectValue(#binding)).inspect_value()
+            })
+        }
+        _ => {
+            let value = expand::field_value(variant, i);
+            quote!(::derive_debug::Node::Leaf(::std::format!("{:?}", #value)))
+        }
+    }
+}
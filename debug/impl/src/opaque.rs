@@ -0,0 +1,101 @@
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{Type, TypeParamBound, TypePath, TypeTraitObject};
+
+// Whether the field's type is one that can never implement Debug, or not
+// usefully, so that it is formatted as opaque without being asked: a function
+// pointer, or anything containing a `dyn Fn`, `dyn FnMut` or `dyn FnOnce`.
+pub fn is_implicit(ty: &Type) -> bool {
+    let mut ty = ty;
+    while let Type::Paren(inner) = ty {
+        ty = &inner.elem;
+    }
+    if let Type::FnPtr(_) = ty {
+        return true;
+    }
+
+    let mut visitor = ClosureVisitor { found: false };
+    visitor.visit_type(ty);
+    visitor.found
+}
+
+struct ClosureVisitor {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for ClosureVisitor {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        // PhantomData<T> is Debug regardless of T.
+        if ty.path.segments.last().unwrap().ident == "PhantomData" {
+            return;
+        }
+        visit::visit_type_path(self, ty);
+    }
+
+    fn visit_type_trait_object(&mut self, ty: &'ast TypeTraitObject) {
+        for bound in &ty.bounds {
+            if let TypeParamBound::Trait(bound) = bound {
+                let ident = &bound.path.segments.last().unwrap().ident;
+                if ident == "Fn" || ident == "FnMut" || ident == "FnOnce" {
+                    self.found = true;
+                }
+            }
+        }
+        visit::visit_type_trait_object(self, ty);
+    }
+}
+
+// The type as it would be written by hand, like `Box<dyn Fn(u8) -> bool>`,
+// rather than with a space between every pair of tokens.
+pub fn type_string(ty: &Type) -> String {
+    let mut string = String::new();
+    write_tokens(ty.to_token_stream(), &mut string);
+    string
+}
+
+fn write_tokens(tokens: TokenStream, string: &mut String) {
+    // Whether the previous token was a word that a following word must be
+    // separated from, as in `dyn Fn` or `&'a str`.
+    let mut word = false;
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if word {
+                    string.push(' ');
+                }
+                string.push_str(&token.to_string());
+                word = true;
+                continue;
+            }
+            TokenTree::Punct(punct) => match punct.as_char() {
+                ',' => string.push_str(", "),
+                ';' => string.push_str("; "),
+                '+' | '=' => {
+                    string.push(' ');
+                    string.push(punct.as_char());
+                    string.push(' ');
+                }
+                '-' if punct.spacing() == Spacing::Joint => {
+                    tokens.next();
+                    string.push_str(" -> ");
+                }
+                ch => string.push(ch),
+            },
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                string.push_str(open);
+                write_tokens(group.stream(), string);
+                string.truncate(string.trim_end().len());
+                string.push_str(close);
+            }
+        }
+        word = false;
+    }
+}
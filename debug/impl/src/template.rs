@@ -0,0 +1,432 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use std::fmt::Display;
+use std::ops::Range;
+use syn::{Error, LitStr, Result};
+
+// A format string whose arguments name fields of the value being formatted,
+// as in `#[display("{name} (mask {bitmask:08b})")]`.
+pub struct Template {
+    lit: LitStr,
+    pieces: Vec<Piece>,
+}
+
+enum Piece {
+    // Literal text, with braces still escaped as `{{` and `}}`.
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+pub struct Placeholder {
+    pub arg: Arg,
+    // Byte range of the argument within the string's value, or of the
+    // whole placeholder for `{}`.
+    range: Range<usize>,
+    // Byte range of the whole placeholder, braces included.
+    whole: Range<usize>,
+    // Fill, alignment, sign and flags, copied verbatim.
+    flags: String,
+    width: Option<Count>,
+    precision: Option<Count>,
+    ty: String,
+}
+
+pub enum Arg {
+    // {}, which in a field's format is the field itself
+    Value,
+    // {name}
+    Named(Ident),
+    // {0}
+    Index(usize),
+}
+
+// Where an argument appears in the template, for error reporting.
+pub struct Location {
+    // The argument itself.
+    range: Range<usize>,
+    // The placeholder containing it.
+    whole: Range<usize>,
+}
+
+enum Count {
+    Literal(usize),
+    Arg(Arg, Range<usize>),
+}
+
+impl Template {
+    pub fn parse(lit: LitStr) -> Result<Self> {
+        let value = lit.value();
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut rest = value.char_indices().peekable();
+
+        while let Some((i, ch)) = rest.next() {
+            match ch {
+                '{' if rest.next_if(|&(_, ch)| ch == '{').is_some() => text.push_str("{{"),
+                '}' if rest.next_if(|&(_, ch)| ch == '}').is_some() => text.push_str("}}"),
+                '{' => {
+                    let Some(end) = value[i..].find('}').map(|end| i + end) else {
+                        let msg = "invalid format string: expected `}` but string was terminated";
+                        let range = i..value.len();
+                        return Err(error(&lit, &value, range.clone(), range, msg));
+                    };
+                    while rest.next_if(|&(j, _)| j <= end).is_some() {}
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    let placeholder = Placeholder::parse(&lit, &value, i + 1..end)?;
+                    pieces.push(Piece::Placeholder(placeholder));
+                }
+                '}' => {
+                    let msg = format!(
+                        "invalid format string: unmatched `}}` found at offset {}",
+                        offset(&value, i),
+                    );
+                    return Err(Error::new(subspan(&lit, i..i + 1), msg));
+                }
+                _ => text.push(ch),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+
+        Ok(Template { lit, pieces })
+    }
+
+    pub fn lit(&self) -> &LitStr {
+        &self.lit
+    }
+
+    pub fn placeholders(&self) -> impl Iterator<Item = &Placeholder> {
+        self.pieces.iter().filter_map(|piece| match piece {
+            Piece::Text(_) => None,
+            Piece::Placeholder(placeholder) => Some(placeholder),
+        })
+    }
+
+    pub fn location(&self, placeholder: &Placeholder) -> Location {
+        Location {
+            range: placeholder.range.clone(),
+            whole: placeholder.whole.clone(),
+        }
+    }
+
+    // Every argument referenced by the template, including widths and
+    // precisions taken from fields, with where to report errors about it.
+    pub fn args(&self) -> impl Iterator<Item = (&Arg, Location)> {
+        self.placeholders()
+            .map(|placeholder| (&placeholder.arg, self.location(placeholder)))
+            .chain(self.counts())
+    }
+
+    // The arguments that give a width or precision, as in `{:width$}`.
+    pub fn counts(&self) -> impl Iterator<Item = (&Arg, Location)> {
+        self.placeholders().flat_map(|placeholder| {
+            let counts = [&placeholder.width, &placeholder.precision];
+            counts.into_iter().filter_map(|count| match count {
+                Some(Count::Arg(arg, range)) => {
+                    let location = Location {
+                        range: range.clone(),
+                        whole: placeholder.whole.clone(),
+                    };
+                    Some((arg, location))
+                }
+                _ => None,
+            })
+        })
+    }
+
+    pub fn span(&self, location: &Location) -> Span {
+        subspan(&self.lit, location.range.clone())
+    }
+
+    // The placeholder at the location, as in "`{x:08b}` at offset 7".
+    pub fn describe(&self, location: &Location) -> String {
+        describe(&self.lit.value(), location.whole.clone())
+    }
+
+    pub fn error(&self, location: &Location, msg: impl Display) -> Error {
+        let value = self.lit.value();
+        error(
+            &self.lit,
+            &value,
+            location.range.clone(),
+            location.whole.clone(),
+            msg,
+        )
+    }
+
+    // Expression of type fmt::Arguments, with `resolve` mapping each argument
+    // to the local variable holding a reference to the field, and to the value
+    // to format under that name, usually the variable itself.
+    pub fn format_args(&self, resolve: impl Fn(&Arg) -> (Ident, TokenStream)) -> TokenStream {
+        let mut format = String::new();
+        let mut names = Vec::new();
+        let mut values = Vec::new();
+        let mut push = |name: Ident, value: TokenStream| {
+            if !names.contains(&name) {
+                names.push(name);
+                values.push(value);
+            }
+        };
+
+        for piece in &self.pieces {
+            let placeholder = match piece {
+                Piece::Text(text) => {
+                    format.push_str(text);
+                    continue;
+                }
+                Piece::Placeholder(placeholder) => placeholder,
+            };
+
+            let (binding, value) = resolve(&placeholder.arg);
+            format.push('{');
+            format.push_str(&binding.to_string());
+            push(binding, value);
+
+            let spec = &placeholder.flags;
+            let counts = [("", &placeholder.width), (".", &placeholder.precision)];
+            if !spec.is_empty()
+                || !placeholder.ty.is_empty()
+                || counts.iter().any(|c| c.1.is_some())
+            {
+                format.push(':');
+                format.push_str(spec);
+            }
+            for (prefix, count) in counts {
+                match count {
+                    Some(Count::Literal(n)) => {
+                        format.push_str(prefix);
+                        format.push_str(&n.to_string());
+                    }
+                    // Counts must be usize, not a reference to one.
+                    Some(Count::Arg(arg, _)) => {
+                        let (binding, _) = resolve(arg);
+                        let name = format_ident!("{}_count", binding);
+                        format.push_str(prefix);
+                        format.push_str(&name.to_string());
+                        format.push('$');
+                        push(name, quote!(*#binding));
+                    }
+                    None => {}
+                }
+            }
+            format.push_str(&placeholder.ty);
+            format.push('}');
+        }
+
+        let format = LitStr::new(&format, self.lit.span());
+        quote!(::std::format_args!(#format, #(#names = #values),*))
+    }
+}
+
+impl Placeholder {
+    fn parse(lit: &LitStr, value: &str, range: Range<usize>) -> Result<Self> {
+        let whole = range.start - 1..range.end + 1;
+        let error = |range: Range<usize>, msg: &str| error(lit, value, range, whole.clone(), msg);
+        let content = &value[range.clone()];
+        let (arg_str, spec_start) = match content.find(':') {
+            Some(colon) => (&content[..colon], range.start + colon + 1),
+            None => (content, range.end),
+        };
+        let mut arg_range = range.start..range.start + arg_str.len();
+        let arg = match parse_arg(arg_str.trim()) {
+            Some(arg) => arg,
+            None if arg_str.trim().is_empty() => {
+                arg_range = range.start - 1..range.end + 1;
+                Arg::Value
+            }
+            None => {
+                let msg = format!("invalid format argument `{}`", arg_str);
+                return Err(error(arg_range, &msg));
+            }
+        };
+
+        let mut spec = Spec {
+            lit,
+            value,
+            pos: spec_start,
+            end: range.end,
+            whole: whole.clone(),
+        };
+        let flags_start = spec.pos;
+        let rest = spec.rest();
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(fill), Some('<' | '^' | '>')) => spec.pos += fill.len_utf8() + 1,
+            (Some('<' | '^' | '>'), _) => spec.pos += 1,
+            _ => {}
+        }
+        spec.eat(|ch| ch == '+' || ch == '-');
+        spec.eat(|ch| ch == '#');
+        if spec.rest().starts_with('0') && !spec.rest()[1..].starts_with('$') {
+            spec.pos += 1;
+        }
+        let flags = value[flags_start..spec.pos].to_owned();
+        let width = spec.count()?;
+        let precision = if spec.eat(|ch| ch == '.') {
+            if spec.eat(|ch| ch == '*') {
+                let msg = "`.*` precision is not supported, name a field as in `.field$`";
+                return Err(error(spec.pos - 2..spec.pos, msg));
+            }
+            match spec.count()? {
+                Some(count) => Some(count),
+                None => {
+                    let msg = "expected precision after `.`";
+                    return Err(error(spec.pos - 1..spec.pos, msg));
+                }
+            }
+        } else {
+            None
+        };
+        let ty = spec.rest().to_owned();
+        let placeholder = Placeholder {
+            arg,
+            range: arg_range,
+            whole: whole.clone(),
+            flags,
+            width,
+            precision,
+            ty,
+        };
+        if placeholder.format_trait().is_none() {
+            let msg = format!("unknown format trait `{}`", placeholder.ty);
+            return Err(error(spec.pos..spec.end, &msg));
+        }
+        Ok(placeholder)
+    }
+
+    // Whether the argument is formatted with Debug, as in `{:?}` or `{:#x?}`.
+    pub fn is_debug(&self) -> bool {
+        self.ty.ends_with('?')
+    }
+
+    // The trait through which the argument is formatted.
+    pub fn format_trait(&self) -> Option<TokenStream> {
+        Some(match self.ty.as_str() {
+            "" => quote!(::std::fmt::Display),
+            "?" | "x?" | "X?" => quote!(::std::fmt::Debug),
+            "x" => quote!(::std::fmt::LowerHex),
+            "X" => quote!(::std::fmt::UpperHex),
+            "o" => quote!(::std::fmt::Octal),
+            "b" => quote!(::std::fmt::Binary),
+            "e" => quote!(::std::fmt::LowerExp),
+            "E" => quote!(::std::fmt::UpperExp),
+            "p" => quote!(::std::fmt::Pointer),
+            _ => return None,
+        })
+    }
+}
+
+struct Spec<'a> {
+    lit: &'a LitStr,
+    value: &'a str,
+    pos: usize,
+    end: usize,
+    whole: Range<usize>,
+}
+
+impl<'a> Spec<'a> {
+    fn rest(&self) -> &'a str {
+        &self.value[self.pos..self.end]
+    }
+
+    fn eat(&mut self, f: impl Fn(char) -> bool) -> bool {
+        match self.rest().chars().next() {
+            Some(ch) if f(ch) => {
+                self.pos += ch.len_utf8();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // integer | argument '$'
+    fn count(&mut self) -> Result<Option<Count>> {
+        let rest = self.rest();
+        let len = rest
+            .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+            .unwrap_or(rest.len());
+        let word = &rest[..len];
+        if rest[len..].starts_with('$') {
+            let range = self.pos..self.pos + len;
+            let Some(arg) = parse_arg(word) else {
+                let msg = format!("invalid count argument `{}`", word);
+                let whole = self.whole.clone();
+                return Err(error(self.lit, self.value, range, whole, msg));
+            };
+            self.pos += len + 1;
+            Ok(Some(Count::Arg(arg, range)))
+        } else {
+            let digits = word.len()
+                - word
+                    .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                    .len();
+            if digits == 0 {
+                return Ok(None);
+            }
+            let n = word[..digits].parse().unwrap();
+            self.pos += digits;
+            Ok(Some(Count::Literal(n)))
+        }
+    }
+}
+
+fn parse_arg(arg: &str) -> Option<Arg> {
+    if let Ok(index) = arg.parse() {
+        Some(Arg::Index(index))
+    } else {
+        let ident = syn::parse_str::<Ident>(arg).ok()?;
+        Some(Arg::Named(ident))
+    }
+}
+
+// An error about part of a placeholder. The span only narrows to that part
+// where the compiler supports it, so the message also says which placeholder
+// it is about.
+fn error(
+    lit: &LitStr,
+    value: &str,
+    range: Range<usize>,
+    whole: Range<usize>,
+    msg: impl Display,
+) -> Error {
+    let msg = format!("{}, in {}", msg, describe(value, whole));
+    Error::new(subspan(lit, range), msg)
+}
+
+fn describe(value: &str, whole: Range<usize>) -> String {
+    let start = offset(value, whole.start);
+    format!("`{}` at offset {}", &value[whole], start)
+}
+
+// Offsets in messages count characters from the start of the string.
+fn offset(value: &str, i: usize) -> usize {
+    value[..i].chars().count()
+}
+
+// Points at part of the string literal when the compiler supports it, and at
+// the whole literal otherwise. Offsets into the value only line up with the
+// source when the literal contains no escapes.
+pub fn subspan(lit: &LitStr, range: Range<usize>) -> Span {
+    let repr = lit.token().to_string();
+    let value = lit.value();
+    let prefix = match repr.find('"') {
+        Some(quote)
+            if repr[..quote]
+                .trim_start_matches('r')
+                .trim_matches('#')
+                .is_empty() =>
+        {
+            quote + 1
+        }
+        _ => return lit.span(),
+    };
+    if repr.get(prefix..prefix + value.len()) != Some(&value) {
+        return lit.span();
+    }
+    lit.token()
+        .subspan(prefix + range.start..prefix + range.end)
+        .unwrap_or_else(|| lit.span())
+}
@@ -0,0 +1,57 @@
+use std::any;
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+
+thread_local! {
+    // Values being formatted on this thread by #[debug(cycle_safe)] impls,
+    // outermost first. A value is identified by its address together with its
+    // type, because a struct and its first field share an address.
+    static FORMATTING: RefCell<Vec<(usize, &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+// #[debug(cycle_safe)]
+//
+// Formats `value` with `fmt`, unless the same value is already being formatted
+// further up the stack, as happens when following the pointers of a cyclic
+// Rc or Arc graph. That repeat prints `<cycle #N>` instead, where N counts the
+// cycle-safe values entered before it, starting from 0 for the outermost.
+pub fn guard<T: ?Sized>(
+    value: &T,
+    formatter: &mut fmt::Formatter,
+    fmt: impl FnOnce(&mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    // Zero-sized values do not have distinct addresses.
+    if mem::size_of_val(value) == 0 {
+        return fmt(formatter);
+    }
+
+    let key = (
+        value as *const T as *const () as usize,
+        any::type_name::<T>(),
+    );
+    let entered = FORMATTING.with(|formatting| {
+        let mut formatting = formatting.borrow_mut();
+        match formatting.iter().position(|entry| *entry == key) {
+            Some(n) => Err(n),
+            None => {
+                formatting.push(key);
+                Ok(Exit)
+            }
+        }
+    });
+
+    match entered {
+        Ok(_exit) => fmt(formatter),
+        Err(n) => write!(formatter, "<cycle #{}>", n),
+    }
+}
+
+// Pops the value when formatting it completes, even by panicking.
+struct Exit;
+
+impl Drop for Exit {
+    fn drop(&mut self) {
+        FORMATTING.with(|formatting| formatting.borrow_mut().pop());
+    }
+}
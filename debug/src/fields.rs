@@ -0,0 +1,9 @@
+use std::fmt;
+
+// Implemented by derive(CustomDebug) for structs with named fields, so that a
+// #[debug(flatten)] field of such a type can write its fields directly into
+// the Debug output of the struct that contains it.
+pub trait DebugFields {
+    // Adds each field to `builder`, returning whether any were left out.
+    fn fmt_fields(&self, builder: &mut fmt::DebugStruct<'_, '_>) -> bool;
+}
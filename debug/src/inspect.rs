@@ -0,0 +1,147 @@
+use std::fmt::{self, Debug, Display};
+
+// Implemented by derive(Inspect), which describes a value as a tree following
+// the same #[debug(...)] attributes as derive(CustomDebug).
+pub trait Inspect {
+    fn inspect(&self) -> Node;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    // A struct, or an enum variant, with named fields.
+    Struct {
+        name: String,
+        fields: Vec<(String, Node)>,
+        // Whether any fields were skipped.
+        non_exhaustive: bool,
+    },
+    // A tuple struct, or a tuple variant.
+    Tuple {
+        name: String,
+        fields: Vec<Node>,
+        non_exhaustive: bool,
+    },
+    // A value of a type that does not implement Inspect, as its Debug
+    // representation, or a unit struct or variant as its name.
+    Leaf(String),
+    // A field marked #[debug(redact)].
+    Redacted,
+}
+
+impl Node {
+    // Renders the tree one field per line, indented by four spaces per level.
+    // The format is meant to be stable, for comparison against snapshots:
+    //
+    //     Request {
+    //         id: 7
+    //         body: Body(
+    //             "text"
+    //         )
+    //         token: <redacted>
+    //         ..
+    //     }
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+        self.render_into(&mut rendered, 0);
+        rendered
+    }
+
+    fn render_into(&self, rendered: &mut String, depth: usize) {
+        let (close, non_exhaustive) = match self {
+            Node::Leaf(leaf) => {
+                // Keeps any continuation lines at the current indentation.
+                let indent = format!("\n{}", "    ".repeat(depth));
+                rendered.push_str(&leaf.replace('\n', &indent));
+                return;
+            }
+            Node::Redacted => {
+                rendered.push_str("<redacted>");
+                return;
+            }
+            Node::Struct {
+                name,
+                fields,
+                non_exhaustive,
+            } => {
+                rendered.push_str(name);
+                if fields.is_empty() && !non_exhaustive {
+                    rendered.push_str(" {}");
+                    return;
+                }
+                rendered.push_str(" {\n");
+                for (name, field) in fields {
+                    indent(rendered, depth + 1);
+                    rendered.push_str(name);
+                    rendered.push_str(": ");
+                    field.render_into(rendered, depth + 1);
+                    rendered.push('\n');
+                }
+                ("}", *non_exhaustive)
+            }
+            Node::Tuple {
+                name,
+                fields,
+                non_exhaustive,
+            } => {
+                rendered.push_str(name);
+                if fields.is_empty() && !non_exhaustive {
+                    rendered.push_str("()");
+                    return;
+                }
+                rendered.push_str("(\n");
+                for field in fields {
+                    indent(rendered, depth + 1);
+                    field.render_into(rendered, depth + 1);
+                    rendered.push('\n');
+                }
+                (")", *non_exhaustive)
+            }
+        };
+
+        if non_exhaustive {
+            indent(rendered, depth + 1);
+            rendered.push_str("..\n");
+        }
+        indent(rendered, depth);
+        rendered.push_str(close);
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.render())
+    }
+}
+
+fn indent(rendered: &mut String, depth: usize) {
+    for _ in 0..depth {
+        rendered.push_str("    ");
+    }
+}
+
+// Field values are inspected if their type implements Inspect, and formatted
+// with Debug otherwise. The choice is made by method resolution, which prefers
+// the impl for `InspectValue` over the one for `&InspectValue` when both
+// apply. Within a generic impl this sees only the bounds on the type
+// parameters, so fields of a parameter's type are always formatted with Debug.
+pub struct InspectValue<'a, T: ?Sized>(pub &'a T);
+
+pub trait ViaInspect {
+    fn inspect_value(&self) -> Node;
+}
+
+impl<'a, T: ?Sized + Inspect> ViaInspect for InspectValue<'a, T> {
+    fn inspect_value(&self) -> Node {
+        self.0.inspect()
+    }
+}
+
+pub trait ViaDebug {
+    fn inspect_value(&self) -> Node;
+}
+
+impl<'a, T: ?Sized + Debug> ViaDebug for &InspectValue<'a, T> {
+    fn inspect_value(&self) -> Node {
+        Node::Leaf(format!("{:?}", self.0))
+    }
+}
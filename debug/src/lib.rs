@@ -1,8 +1,30 @@
-use proc_macro::TokenStream;
+// A crate with the "proc-macro" crate type can only export procedural macros,
+// so the derives live in derive_debug_impl and are re-exported here alongside
+// the helpers that their generated code calls at runtime.
+pub use derive_debug_impl::{CustomDebug, CustomDisplay, Inspect};
 
-#[proc_macro_derive(CustomDebug)]
-pub fn derive(input: TokenStream) -> TokenStream {
-    let _ = input;
+pub use crate::fields::DebugFields;
+pub use crate::inspect::{Inspect, Node};
 
-    unimplemented!()
+mod cycle;
+mod fields;
+mod inspect;
+mod opaque;
+mod preset;
+mod redact;
+mod sorted;
+mod truncate;
+mod with;
+
+// Not public API. Used by generated code.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::cycle::guard as cycle_guard;
+    pub use crate::inspect::{InspectValue, ViaDebug, ViaInspect};
+    pub use crate::opaque::Opaque;
+    pub use crate::preset::{Bin, Bytes, Hex, Hexdump, Width};
+    pub use crate::redact::{Redacted, RedactedHash, RedactedLen};
+    pub use crate::sorted::{DebugSorted, Sorted};
+    pub use crate::truncate::{MaxItems, MaxLen};
+    pub use crate::with::DebugWith;
 }
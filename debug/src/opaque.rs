@@ -0,0 +1,12 @@
+use std::fmt::{self, Debug};
+
+// #[debug(opaque)]
+//
+// Stands in for a value that cannot be formatted, naming its type instead.
+pub struct Opaque(pub &'static str);
+
+impl Debug for Opaque {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "<{}>", self.0)
+    }
+}
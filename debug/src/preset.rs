@@ -0,0 +1,146 @@
+use std::fmt::{self, Binary, Debug, LowerHex, Write};
+
+// The number of bits that #[debug(hex)] and #[debug(bin)] pad an integer to.
+// Fields are formatted through a reference, so a reference to an integer has
+// the width of the integer rather than that of the pointer.
+pub trait Width: sealed::Sealed {
+    const BITS: u32;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! width {
+    ($($int:ty)*) => {
+        $(
+            impl sealed::Sealed for $int {}
+
+            impl Width for $int {
+                const BITS: u32 = <$int>::BITS;
+            }
+        )*
+    };
+}
+
+width!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+impl<T: ?Sized + Width> sealed::Sealed for &T {}
+
+impl<T: ?Sized + Width> Width for &T {
+    const BITS: u32 = T::BITS;
+}
+
+// #[debug(hex)]
+//
+// Zero-padded to the full width of the type, so that a u32 always prints as
+// eight hex digits.
+pub struct Hex<'a, T>(&'a T);
+
+impl<'a, T> Hex<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Hex(value)
+    }
+}
+
+impl<'a, T: LowerHex + Width> Debug for Hex<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let width = T::BITS as usize / 4;
+        write!(formatter, "0x{:0width$x}", self.0, width = width)
+    }
+}
+
+// #[debug(bin)]
+pub struct Bin<'a, T>(&'a T);
+
+impl<'a, T> Bin<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Bin(value)
+    }
+}
+
+impl<'a, T: Binary + Width> Debug for Bin<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let width = T::BITS as usize;
+        write!(formatter, "0b{:0width$b}", self.0, width = width)
+    }
+}
+
+// #[debug(bytes)]
+//
+// Formats bytes the way they would be written as a byte string literal.
+pub struct Bytes<'a, T: ?Sized>(&'a T);
+
+impl<'a, T: ?Sized> Bytes<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Bytes(value)
+    }
+}
+
+impl<'a, T: ?Sized + AsRef<[u8]>> Debug for Bytes<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_bytes(self.0.as_ref(), formatter)
+    }
+}
+
+// #[debug(hexdump)]
+//
+// A byte string in `{:?}` and a hexdump in `{:#?}`, one line per 16 bytes:
+//
+//     00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+pub struct Hexdump<'a, T: ?Sized>(&'a T);
+
+impl<'a, T: ?Sized> Hexdump<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Hexdump(value)
+    }
+}
+
+impl<'a, T: ?Sized + AsRef<[u8]>> Debug for Hexdump<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.0.as_ref();
+        if !formatter.alternate() {
+            return write_bytes(bytes, formatter);
+        }
+        if bytes.is_empty() {
+            return formatter.write_str("[]");
+        }
+
+        formatter.write_str("[\n")?;
+        for (i, line) in bytes.chunks(16).enumerate() {
+            write!(formatter, "    {:08x} ", i * 16)?;
+            for j in 0..16 {
+                if j == 8 {
+                    formatter.write_char(' ')?;
+                }
+                match line.get(j) {
+                    Some(byte) => write!(formatter, " {:02x}", byte)?,
+                    None => formatter.write_str("   ")?,
+                }
+            }
+            formatter.write_str("  |")?;
+            for &byte in line {
+                let ch = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                formatter.write_char(ch)?;
+            }
+            formatter.write_str("|\n")?;
+        }
+        formatter.write_str("]")
+    }
+}
+
+fn write_bytes(bytes: &[u8], formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("b\"")?;
+    for &byte in bytes {
+        if byte == b'\'' {
+            formatter.write_char('\'')?;
+        } else {
+            write!(formatter, "{}", byte.escape_ascii())?;
+        }
+    }
+    formatter.write_str("\"")
+}
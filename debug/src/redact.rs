@@ -0,0 +1,85 @@
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+
+// #[debug(redact)]
+pub struct Redacted;
+
+impl Debug for Redacted {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("<redacted>")
+    }
+}
+
+// #[debug(redact = "len")]
+pub struct RedactedLen(pub usize);
+
+impl Debug for RedactedLen {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "<redacted len={}>", self.0)
+    }
+}
+
+// #[debug(redact = "hash")]
+//
+// The hash is computed with a fixed-key hasher, unlike the randomly seeded one
+// behind HashMap, so that equal values print the same hash from one run of the
+// program to the next and can be correlated across log lines.
+pub struct RedactedHash(u32);
+
+impl RedactedHash {
+    pub fn new<T: ?Sized + Hash>(value: &T) -> Self {
+        let mut hasher = Fnv1a::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+        RedactedHash((hash >> 32) as u32 ^ hash as u32)
+    }
+}
+
+impl Debug for RedactedHash {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "<redacted hash={:08x}>", self.0)
+    }
+}
+
+// 64-bit FNV-1a. Integers are fed in as little-endian u64 where possible so
+// that the result does not depend on the target's endianness or pointer width.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.write_u64(u64::from(n));
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write_u64(u64::from(n));
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
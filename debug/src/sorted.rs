@@ -0,0 +1,59 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Debug};
+
+// #[debug(sorted)]
+//
+// Formats a map or set with its entries in key order, whatever order the
+// collection iterates them in.
+pub struct Sorted<'a, T: ?Sized>(&'a T);
+
+impl<'a, T: ?Sized> Sorted<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Sorted(value)
+    }
+}
+
+impl<'a, T: ?Sized + DebugSorted> Debug for Sorted<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_sorted(formatter)
+    }
+}
+
+pub trait DebugSorted {
+    fn fmt_sorted(&self, formatter: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl<K: Ord + Debug, V: Debug, S> DebugSorted for HashMap<K, V, S> {
+    fn fmt_sorted(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        formatter.debug_map().entries(entries).finish()
+    }
+}
+
+impl<T: Ord + Debug, S> DebugSorted for HashSet<T, S> {
+    fn fmt_sorted(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries: Vec<&T> = self.iter().collect();
+        entries.sort();
+        formatter.debug_set().entries(entries).finish()
+    }
+}
+
+impl<T: ?Sized + DebugSorted> DebugSorted for &T {
+    fn fmt_sorted(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt_sorted(formatter)
+    }
+}
+
+// Already in key order.
+impl<K: Debug, V: Debug> DebugSorted for BTreeMap<K, V> {
+    fn fmt_sorted(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, formatter)
+    }
+}
+
+impl<T: Debug> DebugSorted for BTreeSet<T> {
+    fn fmt_sorted(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, formatter)
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::{self, Debug};
+
+// #[debug(max_items = N)]
+//
+// Formats the first N items of a collection as a list, followed by a count of
+// the items left out.
+pub struct MaxItems<'a, T: ?Sized> {
+    value: &'a T,
+    max: usize,
+}
+
+impl<'a, T: ?Sized> MaxItems<'a, T> {
+    pub fn new(value: &'a T, max: usize) -> Self {
+        MaxItems { value, max }
+    }
+}
+
+impl<'a, T> Debug for MaxItems<'a, T>
+where
+    T: ?Sized,
+    &'a T: IntoIterator,
+    <&'a T as IntoIterator>::Item: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = self.value.into_iter();
+        let mut list = formatter.debug_list();
+        list.entries(items.by_ref().take(self.max));
+        let more = items.count();
+        if more > 0 {
+            list.entry(&More(more));
+        }
+        list.finish()
+    }
+}
+
+// #[debug(max_len = N)]
+//
+// Formats the first N characters of a string, followed by a count of the
+// characters left out.
+pub struct MaxLen<'a, T: ?Sized> {
+    value: &'a T,
+    max: usize,
+}
+
+impl<'a, T: ?Sized> MaxLen<'a, T> {
+    pub fn new(value: &'a T, max: usize) -> Self {
+        MaxLen { value, max }
+    }
+}
+
+impl<'a, T: ?Sized + AsRef<str>> Debug for MaxLen<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let string = self.value.as_ref();
+        match string.char_indices().nth(self.max) {
+            Some((end, _)) => {
+                Debug::fmt(&string[..end], formatter)?;
+                let more = string[end..].chars().count();
+                Debug::fmt(&More(more), formatter)
+            }
+            None => Debug::fmt(string, formatter),
+        }
+    }
+}
+
+struct More(usize);
+
+impl Debug for More {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "... ({} more)", self.0)
+    }
+}
@@ -0,0 +1,20 @@
+use std::fmt::{self, Debug};
+
+// Adapts a `fn(&T, &mut Formatter) -> fmt::Result` given in
+// #[debug(with = "...")] into a Debug impl.
+pub struct DebugWith<'a, T: ?Sized> {
+    value: &'a T,
+    fmt: fn(&T, &mut fmt::Formatter) -> fmt::Result,
+}
+
+impl<'a, T: ?Sized> DebugWith<'a, T> {
+    pub fn new(value: &'a T, fmt: fn(&T, &mut fmt::Formatter) -> fmt::Result) -> Self {
+        DebugWith { value, fmt }
+    }
+}
+
+impl<'a, T: ?Sized> Debug for DebugWith<'a, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        (self.fmt)(self.value, formatter)
+    }
+}
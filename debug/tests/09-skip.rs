@@ -0,0 +1,49 @@
+// Fields marked #[debug(skip)] are left out of the Debug output entirely, which
+// is useful for large buffers or handles that would only add noise. The output
+// ends in `..` to show that some fields were not printed, the same as
+// DebugStruct::finish_non_exhaustive.
+//
+// Skipped fields also take no part in bound inference, so a type parameter
+// that is only mentioned by skipped fields does not get a `T: Debug` bound.
+//
+//     impl<T> Debug for Connection<T> {...}
+//
+//
+// Resources:
+//
+//   - DebugStruct::finish_non_exhaustive:
+//     https://doc.rust-lang.org/std/fmt/struct.DebugStruct.html#method.finish_non_exhaustive
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Connection<T> {
+    name: &'static str,
+    #[debug(skip)]
+    buffer: Vec<u8>,
+    #[debug(skip)]
+    handle: T,
+    #[debug = "0b{:08b}"]
+    flags: u8,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Does not implement Debug.
+    struct Handle;
+
+    let conn = Connection {
+        name: "db",
+        buffer: vec![0; 1024],
+        handle: Handle,
+        flags: 0b101,
+    };
+
+    let debug = format!("{:?}", conn);
+    let expected = r#"Connection { name: "db", flags: 0b00000101, .. }"#;
+    assert_eq!(debug, expected);
+
+    assert_debug::<Connection<Handle>>();
+}
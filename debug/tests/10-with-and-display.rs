@@ -0,0 +1,70 @@
+// Beyond a format string, a field can be printed by a function of the caller's
+// choosing with #[debug(with = "path")]. The function has the same signature as
+// Debug::fmt but receives the field by reference:
+//
+//     fn(&T, &mut fmt::Formatter) -> fmt::Result
+//
+// Since the function decides for itself what it needs from the field's type,
+// such a field contributes no inferred bounds. Use #[debug(bound = "...")] if
+// the function has requirements of its own.
+//
+// Fields marked #[debug(display)] are printed through their Display impl, and
+// infer `T: Display` instead of `T: Debug` for the type parameters they use.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug, Display};
+use std::time::Duration;
+
+fn fmt_duration(duration: &Duration, formatter: &mut fmt::Formatter) -> fmt::Result {
+    write!(formatter, "{}ms", duration.as_millis())
+}
+
+mod opaque {
+    use std::fmt;
+
+    pub fn fmt<T>(_value: &T, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("<opaque>")
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Request<T, U> {
+    #[debug(display)]
+    path: String,
+    #[debug(with = "fmt_duration")]
+    timeout: Duration,
+    #[debug(with = "opaque::fmt")]
+    body: T,
+    #[debug(display)]
+    host: U,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Implements neither Debug nor Display.
+    struct Body;
+
+    // Implements Display but not Debug.
+    struct Host;
+
+    impl Display for Host {
+        fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("example.com")
+        }
+    }
+
+    let request = Request {
+        path: "/index.html".to_owned(),
+        timeout: Duration::from_secs(2),
+        body: Body,
+        host: Host,
+    };
+
+    let debug = format!("{:?}", request);
+    let expected =
+        "Request { path: /index.html, timeout: 2000ms, body: <opaque>, host: example.com }";
+    assert_eq!(debug, expected);
+
+    assert_debug::<Request<Body, Host>>();
+}
@@ -0,0 +1,71 @@
+// Secrets such as passwords and tokens should never make it into logs through
+// a Debug impl. Fields marked #[debug(redact)] print a placeholder instead of
+// their value.
+//
+//   - #[debug(redact)] prints `<redacted>`,
+//   - #[debug(redact = "len")] prints only the length of the value, as given
+//     by its `len` method,
+//   - #[debug(redact = "hash")] prints a short hash of the value, so that log
+//     lines involving the same secret can be correlated without revealing it.
+//     The hash is the same every time the program runs and requires the field
+//     to implement Hash rather than Debug.
+//
+// For structs that consist mostly of sensitive data, a container attribute
+// #[debug(redact_all_except(...))] redacts every field other than the ones
+// listed.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+#[derive(CustomDebug)]
+pub struct Login<T> {
+    user: &'static str,
+    #[debug(redact)]
+    password: String,
+    #[debug(redact = "len")]
+    otp: Vec<u8>,
+    #[debug(redact = "hash")]
+    session: T,
+}
+
+#[derive(CustomDebug)]
+#[debug(redact_all_except(id))]
+pub struct Card {
+    id: u32,
+    number: &'static str,
+    #[debug(redact = "len")]
+    holder: &'static str,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Implements Hash but not Debug.
+    #[derive(Hash)]
+    struct Session(&'static str);
+
+    let login = Login {
+        user: "ferris",
+        password: "hunter2".to_owned(),
+        otp: vec![1, 2, 3, 4, 5, 6],
+        session: Session("d6f0c2"),
+    };
+
+    let debug = format!("{:?}", login);
+    let expected = r#"Login { user: "ferris", password: <redacted>, otp: <redacted len=6>, session: <redacted hash=c818d319> }"#;
+    assert_eq!(debug, expected);
+    assert!(!debug.contains("d6f0c2"));
+
+    let card = Card {
+        id: 7,
+        number: "4111 1111 1111 1111",
+        holder: "Ferris",
+    };
+
+    let debug = format!("{:?}", card);
+    let expected = "Card { id: 7, number: <redacted>, holder: <redacted len=6> }";
+    assert_eq!(debug, expected);
+
+    assert_debug::<Login<Session>>();
+}
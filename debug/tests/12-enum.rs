@@ -0,0 +1,75 @@
+// Enums are printed the same way as the standard library's derive(Debug) would
+// print them: unit variants by name, tuple variants through debug_tuple and
+// struct variants through debug_struct.
+//
+// The field attributes work on fields of variants just like on fields of a
+// struct. In addition, variants accept #[debug(rename = "...")] to print under
+// a different name and #[debug(skip)] to hide the variant's fields.
+//
+// Bounds are inferred from the fields of all variants together, which here
+// gives:
+//
+//     impl<T, E> Debug for State<T, E>
+//     where
+//         T: Debug,
+//     {...}
+//
+// because E is only mentioned in a skipped variant and the field formatted by
+// a custom function.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+
+fn fmt_len<E>(errors: &Vec<E>, formatter: &mut fmt::Formatter) -> fmt::Result {
+    write!(formatter, "{} errors", errors.len())
+}
+
+#[derive(CustomDebug)]
+pub enum State<T, E> {
+    Idle,
+    #[debug(rename = "Busy")]
+    Running(T, #[debug = "{}%"] u8),
+    Done {
+        value: T,
+        #[debug(with = "fmt_len")]
+        errors: Vec<E>,
+        #[debug(skip)]
+        elapsed: u64,
+    },
+    #[debug(skip)]
+    Failed(E),
+}
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Does not implement Debug.
+    struct Error;
+
+    let state: State<&str, Error> = State::Idle;
+    assert_eq!(format!("{:?}", state), "Idle");
+
+    let state: State<&str, Error> = State::Running("build", 40);
+    assert_eq!(format!("{:?}", state), r#"Busy("build", 40%)"#);
+
+    let state: State<&str, Error> = State::Done {
+        value: "ok",
+        errors: vec![Error, Error],
+        elapsed: 10,
+    };
+    let expected = r#"Done { value: "ok", errors: 2 errors, .. }"#;
+    assert_eq!(format!("{:?}", state), expected);
+
+    let state: State<&str, Error> = State::Failed(Error);
+    assert_eq!(format!("{:?}", state), "Failed(..)");
+
+    let state: State<&str, Error> = State::Running("build", 40);
+    let expected = "Busy(\n    \"build\",\n    40%,\n)";
+    assert_eq!(format!("{:#?}", state), expected);
+
+    assert_debug::<State<&str, Error>>();
+    assert_debug::<Never>();
+}
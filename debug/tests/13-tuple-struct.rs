@@ -0,0 +1,37 @@
+// Tuple structs are printed through debug_tuple and unit structs by their name
+// alone, the same as with the standard library's derive(Debug).
+//
+// For newtype wrappers that should be invisible in Debug output, the container
+// attribute #[debug(transparent)] forwards directly to the Debug impl of the
+// single field, including any formatting attribute on that field.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Port(u16);
+
+#[derive(CustomDebug)]
+pub struct Pair<T>(T, #[debug = "{:#x}"] u32);
+
+#[derive(CustomDebug)]
+pub struct Marker;
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct UserId(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Mask {
+    #[debug = "0b{:04b}"]
+    bits: u8,
+}
+
+fn main() {
+    assert_eq!(format!("{:?}", Port(8080)), "Port(8080)");
+    assert_eq!(format!("{:?}", Pair("a", 255)), r#"Pair("a", 0xff)"#);
+    assert_eq!(format!("{:?}", Marker), "Marker");
+    assert_eq!(format!("{:?}", UserId(42)), "42");
+    assert_eq!(format!("{:?}", Mask { bits: 5 }), "0b0101");
+    assert_eq!(format!("{:?}", Some(UserId(42))), "Some(42)");
+}
@@ -0,0 +1,13 @@
+// #[debug(transparent)] only makes sense for a struct that has exactly one
+// field for it to forward to.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
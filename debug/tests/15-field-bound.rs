@@ -0,0 +1,65 @@
+// The per-field form of the escape hatch from test 08. A #[debug(bound = "...")]
+// attribute on a field replaces only the bounds that would have been inferred
+// from that field's type, while inference continues as usual for the other
+// fields:
+//
+//     impl<T: Trait, U, V> Debug for Wrapper<T, U, V>
+//     where
+//         T::Value: Debug,
+//         U: Debug,
+//     {...}
+//
+// An empty string removes the field's bounds entirely, which is useful when the
+// field's type implements Debug regardless of its type parameters.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+pub struct Wrapper<T: Trait, U, V> {
+    #[debug(bound = "T::Value: Debug")]
+    field: Field<T>,
+    normal: U,
+    #[debug(bound = "")]
+    handle: Handle<V>,
+}
+
+#[derive(CustomDebug)]
+struct Field<T: Trait> {
+    values: Vec<T::Value>,
+}
+
+pub struct Handle<V>(PhantomData<V>);
+
+impl<V> Debug for Handle<V> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Handle")
+    }
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Neither implements Debug.
+    struct Id;
+    struct NotDebug;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Wrapper<Id, u8, NotDebug>>();
+
+    let wrapper = Wrapper::<Id, u8, NotDebug> {
+        field: Field { values: vec![1, 2] },
+        normal: 3,
+        handle: Handle(PhantomData),
+    };
+    let expected = "Wrapper { field: Field { values: [1, 2] }, normal: 3, handle: Handle }";
+    assert_eq!(format!("{:?}", wrapper), expected);
+}
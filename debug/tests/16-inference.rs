@@ -0,0 +1,111 @@
+// Bound inference only asks for what the field types actually need.
+//
+// Type parameters that appear only where they cannot affect the field's Debug
+// impl contribute no bound: anywhere inside PhantomData, in a fn pointer's
+// signature, or as a parameter of a trait object's trait. An associated type
+// reached through a qualified path produces a bound on exactly that path, and
+// a field that refers back to the type being derived contributes nothing of
+// its own.
+//
+//     impl<T, U: Source, V> Debug for Inferred<T, U, V>
+//     where
+//         <U as Source>::Item: Debug,
+//     {...}
+//
+// The alternative strategy, #[debug(bound(infer = "fields"))], bounds each
+// field's type as a whole instead of the type parameters it mentions. That
+// suits wrapper types whose Debug impl does not require Debug of their
+// parameters, at the cost of repeating the field types in the where-clause.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+pub trait Source {
+    type Item;
+}
+
+pub trait Callback<T>: Debug {}
+
+#[derive(CustomDebug)]
+pub struct Inferred<'a, T, U: Source, V> {
+    borrowed: PhantomData<&'a T>,
+    produced: PhantomData<fn() -> T>,
+    convert: fn(T) -> V,
+    item: <U as Source>::Item,
+    callback: Box<dyn Callback<V>>,
+}
+
+#[derive(CustomDebug)]
+pub struct List<T> {
+    value: T,
+    next: Option<Box<List<T>>>,
+}
+
+#[derive(CustomDebug)]
+#[debug(bound(infer = "fields"))]
+pub struct Shared<T> {
+    value: Opaque<T>,
+}
+
+// Implements Debug regardless of T.
+pub struct Opaque<T>(Rc<T>);
+
+impl<T> Debug for Opaque<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Opaque")
+    }
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Does not implement Debug.
+    struct NotDebug;
+
+    struct Numbers;
+
+    impl Source for Numbers {
+        type Item = u8;
+    }
+
+    #[derive(Debug)]
+    struct Print;
+
+    impl<T> Callback<T> for Print {}
+
+    assert_debug::<Inferred<NotDebug, Numbers, NotDebug>>();
+    assert_debug::<List<u8>>();
+    assert_debug::<Shared<NotDebug>>();
+
+    fn convert(_: NotDebug) -> NotDebug {
+        NotDebug
+    }
+
+    let inferred = Inferred::<NotDebug, Numbers, NotDebug> {
+        borrowed: PhantomData,
+        produced: PhantomData,
+        convert,
+        item: 7,
+        callback: Box::new(Print),
+    };
+    let debug = format!("{:?}", inferred);
+    assert!(debug.starts_with("Inferred { borrowed: PhantomData<"));
+    assert!(debug.ends_with("item: 7, callback: Print }"));
+
+    let list = List {
+        value: 1,
+        next: Some(Box::new(List {
+            value: 2,
+            next: None,
+        })),
+    };
+    let expected = "List { value: 1, next: Some(List { value: 2, next: None }) }";
+    assert_eq!(format!("{:?}", list), expected);
+
+    let shared = Shared {
+        value: Opaque(Rc::new(NotDebug)),
+    };
+    assert_eq!(format!("{:?}", shared), "Shared { value: Opaque }");
+}
@@ -0,0 +1,89 @@
+// derive(CustomDisplay) generates a Display impl from a template given in a
+// #[display("...")] attribute on the struct, or on each variant of an enum.
+// Placeholders name fields, and take the same format specs as format_args!:
+//
+//     #[display("{name} (mask {bitmask:08b})")]
+//
+// expands to roughly:
+//
+//     impl Display for Permission {
+//         fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+//             match self {
+//                 Self { name: __field0, bitmask: __field1, .. } => formatter.write_fmt(
+//                     format_args!("{__field0} (mask {__field1:08b})", ...),
+//                 ),
+//             }
+//         }
+//     }
+//
+// Tuple fields are referred to by index, and literal braces are written `{{`
+// and `}}`. Bounds are inferred from the fields that the template refers to,
+// using the formatting trait that each placeholder selects.
+
+use derive_debug::CustomDisplay;
+use std::fmt::Display;
+
+#[derive(CustomDisplay)]
+#[display("{name} (mask {bitmask:08b})")]
+pub struct Permission {
+    name: &'static str,
+    bitmask: u8,
+}
+
+#[derive(CustomDisplay)]
+pub enum Shape {
+    #[display("circle of radius {radius:.1}")]
+    Circle { radius: f64 },
+    #[display("{0}x{1} rectangle")]
+    Rectangle(u32, u32),
+    #[display("{{empty}}")]
+    Empty,
+}
+
+#[derive(CustomDisplay)]
+#[display("[{value:>width$}] {value:x?}")]
+pub struct Padded<T> {
+    value: T,
+    width: usize,
+}
+
+#[derive(CustomDisplay)]
+#[display("{label}")]
+pub struct Labeled<T> {
+    label: String,
+    value: T,
+}
+
+fn assert_display<F: Display>() {}
+
+fn main() {
+    // Does not implement Display.
+    struct OnlyDebug;
+
+    let permission = Permission {
+        name: "write",
+        bitmask: 0b10,
+    };
+    assert_eq!(permission.to_string(), "write (mask 00000010)");
+
+    assert_eq!(
+        Shape::Circle { radius: 2.25 }.to_string(),
+        "circle of radius 2.2"
+    );
+    assert_eq!(Shape::Rectangle(3, 4).to_string(), "3x4 rectangle");
+    assert_eq!(Shape::Empty.to_string(), "{empty}");
+
+    let padded = Padded {
+        value: 255,
+        width: 5,
+    };
+    assert_eq!(padded.to_string(), "[  255] ff");
+
+    // T is not referenced by the template, so it needs no bound.
+    assert_display::<Labeled<OnlyDebug>>();
+    let labeled = Labeled {
+        label: "unit".to_owned(),
+        value: OnlyDebug,
+    };
+    assert_eq!(labeled.to_string(), "unit");
+}
@@ -0,0 +1,23 @@
+// Placeholders are resolved against the fields when the derive expands, so a
+// misspelled field name is reported by the derive rather than as an unresolved
+// variable deep inside the generated code. Where the compiler supports spans
+// within a string literal, the error points at just the name. Either way the
+// message quotes the placeholder and gives its offset in characters from the
+// start of the string.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+#[display("{name} (mask {bitmsak:08b})")]
+pub struct Permission {
+    name: &'static str,
+    bitmask: u8,
+}
+
+#[derive(CustomDisplay)]
+pub enum Shape {
+    #[display("{0}x{2} rectangle")]
+    Rectangle(u32, u32),
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+// A field's format string may refer to the other fields of the same struct or
+// enum variant by name. The field itself is the positional argument `{}`.
+//
+//     #[derive(CustomDebug)]
+//     pub struct Measurement<V> {
+//         #[debug("{} {unit}")]
+//         value: V,
+//         #[debug(skip)]
+//         unit: &'static str,
+//     }
+//
+// Every referenced field contributes the bounds that its placeholder needs, so
+// the impl above requires `V: Display` for `{}` and nothing of `unit`, whose
+// type is not generic. Misspelled names are reported by the derive.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Measurement<V> {
+    #[debug("{} {unit}")]
+    value: V,
+    #[debug(skip)]
+    unit: &'static str,
+}
+
+#[derive(CustomDebug)]
+pub enum Reading<S> {
+    Scaled {
+        #[debug = "{}e{exponent} ({source:?})"]
+        mantissa: i64,
+        exponent: i8,
+        source: S,
+    },
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Implements Debug but not Display, which is fine for `{source:?}`.
+    #[derive(Debug)]
+    struct Sensor;
+
+    assert_debug::<Measurement<u64>>();
+    assert_debug::<Reading<Sensor>>();
+
+    let measurement = Measurement {
+        value: 42u64,
+        unit: "ms",
+    };
+    let debug = format!("{:?}", measurement);
+    assert_eq!(debug, "Measurement { value: 42 ms, .. }");
+
+    let reading = Reading::Scaled {
+        mantissa: 15,
+        exponent: -1,
+        source: Sensor,
+    };
+    let debug = format!("{:?}", reading);
+    assert_eq!(
+        debug,
+        "Scaled { mantissa: 15e-1 (Sensor), exponent: -1, source: Sensor }",
+    );
+}
@@ -0,0 +1,13 @@
+// Names in a field's format string must refer to fields of the same struct or
+// enum variant.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Measurement {
+    #[debug("{} {units}")]
+    value: u64,
+    unit: &'static str,
+}
+
+fn main() {}
@@ -0,0 +1,76 @@
+// Large collections and strings can be cut short in the Debug output.
+//
+// #[debug(max_items = N)] applies to any field that can be iterated by
+// reference, printing the first N items as a list followed by the number of
+// items left out. #[debug(max_len = N)] applies to any field that is AsRef<str>
+// and keeps the first N characters.
+//
+//     Packet { payload: [1, 2, 3, ... (997 more)], note: "abcd"... (22 more) }
+//
+// The bound inferred for max_items is on the items, as in `T: Debug` for a
+// Vec<T>, even under #[debug(bound(infer = "fields"))].
+
+use derive_debug::CustomDebug;
+use std::collections::BTreeSet;
+
+#[derive(CustomDebug)]
+pub struct Packet {
+    #[debug(max_items = 3)]
+    payload: Vec<u8>,
+    #[debug(max_len = 4)]
+    note: String,
+}
+
+#[derive(CustomDebug)]
+pub struct Tags<'a> {
+    #[debug(max_items = 2)]
+    names: BTreeSet<&'a str>,
+    #[debug(max_len = 8)]
+    short: &'a str,
+}
+
+#[derive(CustomDebug)]
+#[debug(bound(infer = "fields"))]
+pub struct Window<T> {
+    #[debug(max_items = 2)]
+    items: Vec<T>,
+}
+
+fn main() {
+    let packet = Packet {
+        payload: (1..=1000).map(|i| i as u8).collect(),
+        note: "abcdefghijklmnopqrstuvwxyz".to_owned(),
+    };
+
+    let debug = format!("{:?}", packet);
+    let expected = r#"Packet { payload: [1, 2, 3, ... (997 more)], note: "abcd"... (22 more) }"#;
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:#?}", packet);
+    let expected = r#"Packet {
+    payload: [
+        1,
+        2,
+        3,
+        ... (997 more),
+    ],
+    note: "abcd"... (22 more),
+}"#;
+    assert_eq!(debug, expected);
+
+    // Nothing to truncate.
+    let tags = Tags {
+        names: BTreeSet::from(["a", "b"]),
+        short: "ünïcödé",
+    };
+    let debug = format!("{:?}", tags);
+    assert_eq!(debug, r#"Tags { names: ["a", "b"], short: "ünïcödé" }"#);
+
+    let window = Window {
+        items: vec![1, 2, 3],
+    };
+    assert_eq!(
+        format!("{:?}", window),
+        "Window { items: [1, 2, ... (1 more)] }"
+    );
+}
@@ -0,0 +1,38 @@
+// Format strings in #[debug = "..."] are parsed by the derive, which reports
+// mistakes at the attribute instead of deep inside the generated format_args!
+// call. Where the compiler supports spans within a string literal, the error
+// points at the offending characters. Since that is not always possible, the
+// message also quotes the placeholder at fault and gives its offset in
+// characters from the start of the string.
+//
+// A field's format string needs exactly one placeholder for the field itself,
+// and every placeholder must select one of the formatting traits known to
+// format_args!.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct UnknownTrait {
+    #[debug = "0b{:08q}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct TwoPlaceholders {
+    #[debug = "{} and {}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct NoPlaceholder {
+    #[debug = "value"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Unterminated {
+    #[debug = "{:x"]
+    value: u8,
+}
+
+fn main() {}
@@ -0,0 +1,75 @@
+// Presets for the formats that register and protocol structs need most often.
+//
+//   - #[debug(hex)] prints `0x` and the value zero-padded to the width of its
+//     type, for example `0x0000beef` for a u32 or a &u32. It applies to the
+//     primitive integer types and references to them.
+//   - #[debug(bin)] does the same in binary.
+//   - #[debug(bytes)] prints a [u8] or Vec<u8> as a byte string literal, with
+//     escapes for anything that is not printable ASCII.
+//   - #[debug(hexdump)] is the same as `bytes` in {:?} mode, and a hexdump in
+//     {:#?} mode.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Register {
+    #[debug(hex)]
+    address: u32,
+    #[debug(hex)]
+    flags: u8,
+    #[debug(bin)]
+    mask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Refs<'a> {
+    #[debug(hex)]
+    r: &'a u32,
+    #[debug(bin)]
+    b: &'a &'a u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Frame<'a> {
+    #[debug(bytes)]
+    header: &'a [u8],
+    #[debug(hexdump)]
+    payload: Vec<u8>,
+}
+
+fn main() {
+    let register = Register {
+        address: 0xbeef,
+        flags: 0xa,
+        mask: 0b101,
+    };
+    let debug = format!("{:?}", register);
+    let expected = "Register { address: 0x0000beef, flags: 0x0a, mask: 0b00000101 }";
+    assert_eq!(debug, expected);
+
+    let refs = Refs {
+        r: &0xbeef,
+        b: &&0b101,
+    };
+    let debug = format!("{:?}", refs);
+    assert_eq!(debug, "Refs { r: 0x0000beef, b: 0b00000101 }");
+
+    let frame = Frame {
+        header: b"GET \"/\"\r\n\x00",
+        payload: b"Hello, world!\n\x00\x01\x02\x03".to_vec(),
+    };
+    let debug = format!("{:?}", frame);
+    let expected =
+        r#"Frame { header: b"GET \"/\"\r\n\x00", payload: b"Hello, world!\n\x00\x01\x02\x03" }"#;
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:#?}", frame);
+    let expected = r#"Frame {
+    header: b"GET \"/\"\r\n\x00",
+    payload: [
+        00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+        00000010  02 03                                             |..|
+    ],
+}"#;
+    assert_eq!(debug, expected);
+}
@@ -0,0 +1,83 @@
+// #[debug(flatten)] writes the fields of a nested struct directly into the
+// Debug output of the struct that contains it, instead of as one more level of
+// nesting.
+//
+//     Request { id: 7, method: "GET", path: "/" }
+//
+// rather than:
+//
+//     Request { common: Common { id: 7, method: "GET" }, path: "/" }
+//
+// This works through the DebugFields trait, which derive(CustomDebug) also
+// implements for every struct with named fields. Fields that the nested struct
+// skips make the outer struct print `..` as well.
+
+use derive_debug::{CustomDebug, DebugFields};
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Common {
+    id: u32,
+    method: &'static str,
+    #[debug(skip)]
+    internal: bool,
+}
+
+#[derive(CustomDebug)]
+pub struct Request {
+    #[debug(flatten)]
+    common: Common,
+    path: &'static str,
+}
+
+#[derive(CustomDebug)]
+pub struct Labels<T> {
+    label: T,
+}
+
+#[derive(CustomDebug)]
+pub enum Event<T> {
+    Tagged {
+        #[debug(flatten)]
+        labels: Labels<T>,
+        count: usize,
+    },
+}
+
+fn assert_debug_fields<F: DebugFields>() {}
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug_fields::<Common>();
+    assert_debug_fields::<Labels<u8>>();
+    assert_debug::<Event<u8>>();
+
+    let request = Request {
+        common: Common {
+            id: 7,
+            method: "GET",
+            internal: true,
+        },
+        path: "/",
+    };
+
+    let debug = format!("{:?}", request);
+    let expected = r#"Request { id: 7, method: "GET", path: "/", .. }"#;
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:#?}", request);
+    let expected = r#"Request {
+    id: 7,
+    method: "GET",
+    path: "/",
+    ..
+}"#;
+    assert_eq!(debug, expected);
+
+    let event = Event::Tagged {
+        labels: Labels { label: "disk" },
+        count: 2,
+    };
+    let debug = format!("{:?}", event);
+    assert_eq!(debug, r#"Tagged { label: "disk", count: 2 }"#);
+}
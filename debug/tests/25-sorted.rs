@@ -0,0 +1,83 @@
+// HashMap and HashSet iterate in a different order from one run of the
+// program to the next, which makes their Debug output useless for snapshot
+// tests. #[debug(sorted)] prints the entries of a map or set in key order
+// instead, and requires the keys to implement Ord. The field may also be a
+// reference to a map or set.
+//
+// The container attribute #[debug(deterministic)] applies the same to every
+// field whose type is written as HashMap or HashSet.
+
+use derive_debug::CustomDebug;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Inventory {
+    #[debug(sorted)]
+    counts: HashMap<&'static str, u32>,
+    owner: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug(deterministic)]
+pub struct Index<K> {
+    ids: HashSet<u64>,
+    names: HashMap<K, Vec<u64>>,
+}
+
+#[derive(CustomDebug)]
+pub struct View<'a> {
+    #[debug(sorted)]
+    counts: &'a HashMap<u8, u8>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug::<Index<String>>();
+
+    let inventory = Inventory {
+        counts: HashMap::from([("pear", 3), ("apple", 1), ("fig", 7), ("kiwi", 2)]),
+        owner: "ann",
+    };
+    let debug = format!("{:?}", inventory);
+    let expected =
+        r#"Inventory { counts: {"apple": 1, "fig": 7, "kiwi": 2, "pear": 3}, owner: "ann" }"#;
+    assert_eq!(debug, expected);
+
+    let index = Index {
+        ids: HashSet::from([30, 10, 20, 50, 40]),
+        names: HashMap::from([("b", vec![20]), ("a", vec![10, 30])]),
+    };
+    let debug = format!("{:?}", index);
+    let expected = r#"Index { ids: {10, 20, 30, 40, 50}, names: {"a": [10, 30], "b": [20]} }"#;
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:#?}", index);
+    let expected = r#"Index {
+    ids: {
+        10,
+        20,
+        30,
+        40,
+        50,
+    },
+    names: {
+        "a": [
+            10,
+            30,
+        ],
+        "b": [
+            20,
+        ],
+    },
+}"#;
+    assert_eq!(debug, expected);
+
+    let counts = HashMap::from([(3, 30), (1, 10), (2, 20)]);
+    let view = View { counts: &counts };
+    assert_eq!(
+        format!("{:?}", view),
+        "View { counts: {1: 10, 2: 20, 3: 30} }",
+    );
+}
@@ -0,0 +1,52 @@
+// Fields that cannot be formatted, such as closures, can be marked
+// #[debug(opaque)] to print their type in angle brackets instead. Opaque fields
+// add no bounds to the Debug impl.
+//
+//     Button { label: "OK", on_click: <Box<dyn Fn(u32) -> bool>> }
+//
+// Fields whose type is a function pointer or contains `dyn Fn`, `dyn FnMut` or
+// `dyn FnOnce` are opaque without the attribute.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+#[derive(CustomDebug)]
+pub struct Button {
+    label: &'static str,
+    on_click: Box<dyn Fn(u32) -> bool>,
+}
+
+#[derive(CustomDebug)]
+pub struct Worker<'a, T> {
+    #[debug(opaque)]
+    state: T,
+    hooks: Vec<Arc<dyn FnMut(&'a str) + Send>>,
+    parse: fn(&str) -> Option<T>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Does not implement Debug.
+    struct State;
+
+    assert_debug::<Worker<State>>();
+
+    let button = Button {
+        label: "OK",
+        on_click: Box::new(|clicks| clicks > 1),
+    };
+    let debug = format!("{:?}", button);
+    let expected = r#"Button { label: "OK", on_click: <Box<dyn Fn(u32) -> bool>> }"#;
+    assert_eq!(debug, expected);
+
+    let worker = Worker {
+        state: State,
+        hooks: Vec::new(),
+        parse: |_| None,
+    };
+    let debug = format!("{:?}", worker);
+    let expected = "Worker { state: <T>, hooks: <Vec<Arc<dyn FnMut(&'a str) + Send>>>, parse: <fn(&str) -> Option<T>> }";
+    assert_eq!(debug, expected);
+}
@@ -0,0 +1,58 @@
+// Following the pointers of a cyclic Rc or Arc graph, Debug would recurse until
+// the stack overflows. With #[debug(cycle_safe)] the generated impl keeps track
+// of the values it is in the middle of formatting, and prints `<cycle #N>` when
+// it reaches one of them again. N identifies the repeated value by how many
+// cycle-safe values were entered before it, so `<cycle #0>` refers back to the
+// outermost one.
+
+use derive_debug::CustomDebug;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+#[derive(CustomDebug)]
+#[debug(cycle_safe)]
+pub struct Node {
+    name: &'static str,
+    edges: Vec<Rc<RefCell<Node>>>,
+}
+
+#[derive(CustomDebug)]
+#[debug(cycle_safe)]
+pub struct Tree {
+    #[debug(display)]
+    label: char,
+    #[debug(skip)]
+    parent: Weak<Tree>,
+}
+
+fn main() {
+    let a = Rc::new(RefCell::new(Node {
+        name: "a",
+        edges: Vec::new(),
+    }));
+    let b = Rc::new(RefCell::new(Node {
+        name: "b",
+        edges: vec![a.clone()],
+    }));
+    a.borrow_mut().edges.push(b.clone());
+
+    let debug = format!("{:?}", a.borrow());
+    let expected = r#"Node { name: "a", edges: [RefCell { value: Node { name: "b", edges: [RefCell { value: <cycle #0> }] } }] }"#;
+    assert_eq!(debug, expected);
+
+    // Formatting the same value twice in a row is not a cycle.
+    let debug = format!("{:?}", b.borrow());
+    let expected = r#"Node { name: "b", edges: [RefCell { value: Node { name: "a", edges: [RefCell { value: <cycle #0> }] } }] }"#;
+    assert_eq!(debug, expected);
+    let twice = format!("{:?} {:?}", b.borrow(), b.borrow());
+    assert_eq!(twice, format!("{} {}", expected, expected));
+
+    let tree = Tree {
+        label: 'x',
+        parent: Weak::new(),
+    };
+    assert_eq!(format!("{:?}", tree), "Tree { label: x, .. }");
+
+    // Break the cycle so the nodes are freed.
+    a.borrow_mut().edges.clear();
+}
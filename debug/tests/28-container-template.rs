@@ -0,0 +1,94 @@
+// A format string given directly in a container attribute replaces the whole
+// rendering of a struct, which suits small value types:
+//
+//     #[derive(CustomDebug)]
+//     #[debug("({x}, {y})")]
+//     pub struct Point {
+//         x: i32,
+//         y: i32,
+//     }
+//
+// prints `(3, 4)`. Placeholders name fields, or refer to them by index in a
+// tuple struct, and take the same format specs as format_args!. Bounds are
+// inferred only from the fields that the template refers to.
+//
+// Field attributes still apply to the fields that the template formats with
+// `?`: a redacted field prints as `<redacted>`, and a field with a format like
+// #[debug(hex)] prints in that format. The same holds for the redaction of
+// #[debug(redact_all_except(...))].
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+#[debug("({x}, {y})")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug("#{0:06x}")]
+pub struct Rgb(u32);
+
+#[derive(CustomDebug)]
+#[debug("{value:?} {unit}")]
+pub struct Quantity<V, U> {
+    value: V,
+    unit: &'static str,
+    marker: PhantomData<U>,
+    cache: U,
+}
+
+#[derive(CustomDebug)]
+#[debug("{user}:{password:?} ({flags:?})")]
+pub struct Login {
+    user: &'static str,
+    #[debug(redact)]
+    password: &'static str,
+    #[debug(hex)]
+    flags: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug("{user:?} via {token:?}")]
+#[debug(redact_all_except(user))]
+pub struct Session {
+    user: &'static str,
+    token: &'static str,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Does not implement Debug.
+    struct Meters;
+
+    assert_debug::<Quantity<f64, Meters>>();
+
+    assert_eq!(format!("{:?}", Point { x: 3, y: 4 }), "(3, 4)");
+    assert_eq!(format!("{:#?}", Point { x: 3, y: 4 }), "(3, 4)");
+    assert_eq!(format!("{:?}", Rgb(0xff8800)), "#ff8800");
+
+    let quantity = Quantity {
+        value: 2.5,
+        unit: "m",
+        marker: PhantomData::<Meters>,
+        cache: Meters,
+    };
+    assert_eq!(format!("{:?}", quantity), "2.5 m");
+
+    let login = Login {
+        user: "bob",
+        password: "hunter2",
+        flags: 5,
+    };
+    assert_eq!(format!("{:?}", login), "bob:<redacted> (0x05)");
+
+    let session = Session {
+        user: "bob",
+        token: "hunter2",
+    };
+    assert_eq!(format!("{:?}", session), "\"bob\" via <redacted>");
+}
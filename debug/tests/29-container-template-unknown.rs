@@ -0,0 +1,40 @@
+// Fields named by a container-level template must exist and must not be
+// skipped. A redacted field, or one with its own format, can only be formatted
+// with `?`, since that is the only way to apply its attributes to it; naming it
+// in `{field}` would otherwise print it as if it had no attributes at all.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug("({x}, {z})")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug("{user}:{password}")]
+pub struct Login {
+    user: &'static str,
+    #[debug(redact)]
+    password: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug("{user}:{password}")]
+#[debug(redact_all_except(user))]
+pub struct Credentials {
+    user: &'static str,
+    password: &'static str,
+}
+
+#[derive(CustomDebug)]
+#[debug("{flags:08b} {cache:?}")]
+pub struct Entry {
+    #[debug(hex)]
+    flags: u8,
+    #[debug(skip)]
+    cache: Vec<u8>,
+}
+
+fn main() {}
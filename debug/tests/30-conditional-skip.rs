@@ -0,0 +1,62 @@
+// Fields can be left out of the Debug output depending on the build or on
+// their value.
+//
+//   - #[debug(skip_if_cfg = "...")] takes a cfg predicate, evaluated with the
+//     configuration of the crate that derives Debug. The field is skipped in
+//     builds where the predicate holds.
+//   - #[debug(skip_if = "...")] takes the path of a function from `&T` to bool
+//     that is called each time the value is formatted.
+//
+// A struct that skipped any fields ends in `..`, as with #[debug(skip)].
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Connection {
+    peer: &'static str,
+    #[debug(skip_if_cfg = "not(debug_assertions)")]
+    trace_id: u64,
+    #[debug(skip_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+#[derive(CustomDebug)]
+pub struct Build(
+    #[debug(skip_if_cfg = "all()")] u8,
+    #[debug(skip_if_cfg = "any()")] u8,
+    #[debug(skip_if = "is_zero", redact)] u32,
+);
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+fn main() {
+    let connection = Connection {
+        peer: "db",
+        trace_id: 9,
+        error: None,
+    };
+    let debug = format!("{:?}", connection);
+    let expected = if cfg!(debug_assertions) {
+        r#"Connection { peer: "db", trace_id: 9, .. }"#
+    } else {
+        r#"Connection { peer: "db", .. }"#
+    };
+    assert_eq!(debug, expected);
+
+    let connection = Connection {
+        error: Some("timeout"),
+        ..connection
+    };
+    let debug = format!("{:?}", connection);
+    let expected = if cfg!(debug_assertions) {
+        r#"Connection { peer: "db", trace_id: 9, error: Some("timeout") }"#
+    } else {
+        r#"Connection { peer: "db", error: Some("timeout"), .. }"#
+    };
+    assert_eq!(debug, expected);
+
+    assert_eq!(format!("{:?}", Build(1, 2, 0)), "Build(2, ..)");
+    assert_eq!(format!("{:?}", Build(1, 2, 3)), "Build(2, <redacted>, ..)");
+}
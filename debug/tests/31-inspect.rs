@@ -0,0 +1,137 @@
+// #[derive(Inspect)] describes a value as a tree rather than a string, for
+// tools that want to walk it or compare it against a snapshot. It reads the
+// same #[debug(...)] attributes as CustomDebug, so that skipped fields are
+// missing, redacted fields are marked as such, and formats like hex are
+// applied to the leaves.
+//
+// Fields whose type also derives Inspect become nested nodes. Every other
+// field is a leaf holding its Debug representation.
+//
+// A struct with a container template is a leaf holding the formatted
+// template, in which redaction and formats apply just as in its Debug output.
+//
+// Node::render gives a stable, indented rendering of the tree.
+
+use derive_debug::{Inspect, Node};
+
+#[derive(Inspect)]
+pub struct Request {
+    id: u32,
+    #[debug(hex)]
+    flags: u8,
+    body: Body,
+    #[debug(redact)]
+    token: String,
+    #[debug(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(Inspect)]
+pub enum Body {
+    Empty,
+    Text(String),
+    Form {
+        fields: Vec<(&'static str, &'static str)>,
+    },
+}
+
+#[derive(Inspect)]
+pub struct Page {
+    number: u32,
+    #[debug(flatten)]
+    meta: Meta,
+}
+
+#[derive(Inspect)]
+pub struct Meta {
+    title: &'static str,
+    #[debug(skip)]
+    draft: bool,
+}
+
+#[derive(Inspect)]
+#[debug("{user}:{password:?}")]
+pub struct Login {
+    user: &'static str,
+    #[debug(redact)]
+    password: &'static str,
+}
+
+fn main() {
+    let request = Request {
+        id: 7,
+        flags: 3,
+        body: Body::Text("text".to_owned()),
+        token: "secret".to_owned(),
+        cache: Vec::new(),
+    };
+
+    let expected = Node::Struct {
+        name: "Request".to_owned(),
+        fields: vec![
+            ("id".to_owned(), Node::Leaf("7".to_owned())),
+            ("flags".to_owned(), Node::Leaf("0x03".to_owned())),
+            (
+                "body".to_owned(),
+                Node::Tuple {
+                    name: "Text".to_owned(),
+                    fields: vec![Node::Leaf("\"text\"".to_owned())],
+                    non_exhaustive: false,
+                },
+            ),
+            ("token".to_owned(), Node::Redacted),
+        ],
+        non_exhaustive: true,
+    };
+    assert_eq!(derive_debug::Inspect::inspect(&request), expected);
+
+    let rendered = "\
+Request {
+    id: 7
+    flags: 0x03
+    body: Text(
+        \"text\"
+    )
+    token: <redacted>
+    ..
+}";
+    assert_eq!(expected.render(), rendered);
+    assert_eq!(expected.to_string(), rendered);
+
+    let body = Body::Form {
+        fields: vec![("a", "1")],
+    };
+    let rendered = "\
+Form {
+    fields: [(\"a\", \"1\")]
+}";
+    assert_eq!(derive_debug::Inspect::inspect(&body).render(), rendered);
+    assert_eq!(
+        derive_debug::Inspect::inspect(&Body::Empty).render(),
+        "Empty"
+    );
+
+    let page = Page {
+        number: 2,
+        meta: Meta {
+            title: "Intro",
+            draft: false,
+        },
+    };
+    let rendered = "\
+Page {
+    number: 2
+    title: \"Intro\"
+    ..
+}";
+    assert_eq!(derive_debug::Inspect::inspect(&page).render(), rendered);
+
+    let login = Login {
+        user: "bob",
+        password: "hunter2",
+    };
+    assert_eq!(
+        derive_debug::Inspect::inspect(&login),
+        Node::Leaf("bob:<redacted>".to_owned()),
+    );
+}
@@ -0,0 +1,85 @@
+// Names can be changed in the output to match what readers of the logs expect
+// rather than what Rust allows.
+//
+//   - #[debug(rename = "...")] on a field or on the struct prints it under the
+//     given name, as it already does on enum variants.
+//   - #[debug(rename_all = "...")] on the container renames the fields of a
+//     struct, or the variants of an enum, that are not renamed individually.
+//     On an enum it renames the variants but not their fields. The supported
+//     rules are "camelCase" and "SCREAMING_SNAKE_CASE".
+//
+// Raw identifiers print without their `r#` prefix.
+//
+// Renaming applies equally to derive(Inspect) and to flattened fields.
+
+use derive_debug::{CustomDebug, Inspect};
+
+#[derive(CustomDebug, Inspect)]
+#[debug(rename = "Packet")]
+pub struct WirePacket {
+    r#type: u8,
+    #[debug(rename = "src")]
+    src_addr_v4: [u8; 4],
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "camelCase")]
+pub struct Header {
+    content_length: usize,
+    #[debug(rename = "ttl")]
+    time_to_live: u32,
+    #[debug(flatten)]
+    options: Options,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct Options {
+    keep_alive: bool,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    NotFound,
+    #[debug(rename = "teapot")]
+    Teapot,
+    TimedOut {
+        after_ms: u64,
+    },
+    r#Unknown(u16),
+}
+
+fn main() {
+    let packet = WirePacket {
+        r#type: 1,
+        src_addr_v4: [10, 0, 0, 1],
+    };
+    let debug = format!("{:?}", packet);
+    assert_eq!(debug, "Packet { type: 1, src: [10, 0, 0, 1] }");
+    let rendered = "\
+Packet {
+    type: 1
+    src: [10, 0, 0, 1]
+}";
+    assert_eq!(derive_debug::Inspect::inspect(&packet).render(), rendered);
+
+    let header = Header {
+        content_length: 12,
+        time_to_live: 64,
+        options: Options { keep_alive: true },
+    };
+    let debug = format!("{:?}", header);
+    assert_eq!(
+        debug,
+        "Header { contentLength: 12, ttl: 64, KEEP_ALIVE: true }",
+    );
+
+    assert_eq!(format!("{:?}", Status::NotFound), "NOT_FOUND");
+    assert_eq!(format!("{:?}", Status::Teapot), "teapot");
+    assert_eq!(
+        format!("{:?}", Status::TimedOut { after_ms: 30 }),
+        "TIMED_OUT { after_ms: 30 }",
+    );
+    assert_eq!(format!("{:?}", Status::Unknown(599)), "UNKNOWN(599)");
+}
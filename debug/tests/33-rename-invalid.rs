@@ -0,0 +1,22 @@
+// Renaming is checked where it is written: a tuple field has no name to
+// replace, an enum's own name is never printed, and rename_all only knows the
+// rules listed in the previous test.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Meters(#[debug(rename = "m")] f64);
+
+#[derive(CustomDebug)]
+#[debug(rename = "Mode")]
+pub enum ModeKind {
+    Fast,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "kebab-case")]
+pub struct Config {
+    max_size: usize,
+}
+
+fn main() {}
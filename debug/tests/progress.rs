@@ -1,12 +1,37 @@
 #[test]
 fn tests() {
     let t = trybuild::TestCases::new();
-    //t.pass("tests/01-parse.rs");
-    //t.pass("tests/02-impl-debug.rs");
-    //t.pass("tests/03-custom-format.rs");
-    //t.pass("tests/04-type-parameter.rs");
-    //t.pass("tests/05-phantom-data.rs");
-    //t.pass("tests/06-bound-trouble.rs");
-    //t.pass("tests/07-associated-type.rs");
-    //t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/01-parse.rs");
+    t.pass("tests/02-impl-debug.rs");
+    t.pass("tests/03-custom-format.rs");
+    t.pass("tests/04-type-parameter.rs");
+    t.pass("tests/05-phantom-data.rs");
+    t.pass("tests/06-bound-trouble.rs");
+    t.pass("tests/07-associated-type.rs");
+    t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-skip.rs");
+    t.pass("tests/10-with-and-display.rs");
+    t.pass("tests/11-redact.rs");
+    t.pass("tests/12-enum.rs");
+    t.pass("tests/13-tuple-struct.rs");
+    t.compile_fail("tests/14-transparent-wrong.rs");
+    t.pass("tests/15-field-bound.rs");
+    t.pass("tests/16-inference.rs");
+    t.pass("tests/17-display.rs");
+    t.compile_fail("tests/18-display-unknown-field.rs");
+    t.pass("tests/19-sibling-format.rs");
+    t.compile_fail("tests/20-sibling-format-unknown.rs");
+    t.pass("tests/21-truncate.rs");
+    t.compile_fail("tests/22-format-validation.rs");
+    t.pass("tests/23-presets.rs");
+    t.pass("tests/24-flatten.rs");
+    t.pass("tests/25-sorted.rs");
+    t.pass("tests/26-opaque.rs");
+    t.pass("tests/27-cycle-safe.rs");
+    t.pass("tests/28-container-template.rs");
+    t.compile_fail("tests/29-container-template-unknown.rs");
+    t.pass("tests/30-conditional-skip.rs");
+    t.pass("tests/31-inspect.rs");
+    t.pass("tests/32-rename.rs");
+    t.compile_fail("tests/33-rename-invalid.rs");
 }